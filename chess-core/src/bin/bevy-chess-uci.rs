@@ -0,0 +1,10 @@
+//! The chess rules and search of `chess_core` as a UCI engine, for chess GUIs
+//! and test harnesses.
+
+use std::io;
+
+use chess_core::uci::UciServer;
+
+fn main() -> io::Result<()> {
+    UciServer::new(io::stdout()).run(io::stdin().lock())
+}
@@ -0,0 +1,70 @@
+use crate::Color;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CastlingSide {
+    KingSide,
+    QueenSide,
+}
+
+impl CastlingSide {
+    pub fn rook_file(self) -> u8 {
+        match self {
+            CastlingSide::KingSide => 7,
+            CastlingSide::QueenSide => 0,
+        }
+    }
+
+    /// File the king lands on after castling.
+    pub fn king_target_file(self) -> u8 {
+        match self {
+            CastlingSide::KingSide => 6,
+            CastlingSide::QueenSide => 2,
+        }
+    }
+
+    /// File the rook lands on after castling.
+    pub fn rook_target_file(self) -> u8 {
+        match self {
+            CastlingSide::KingSide => 5,
+            CastlingSide::QueenSide => 3,
+        }
+    }
+}
+
+/// Which castling moves are still available to each side.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub struct CastlingRights(u8);
+
+impl CastlingRights {
+    pub const NONE: CastlingRights = CastlingRights(0);
+    pub const ALL: CastlingRights = CastlingRights(0b1111);
+
+    fn bit(color: Color, side: CastlingSide) -> u8 {
+        match (color, side) {
+            (Color::White, CastlingSide::KingSide) => 0b0001,
+            (Color::White, CastlingSide::QueenSide) => 0b0010,
+            (Color::Black, CastlingSide::KingSide) => 0b0100,
+            (Color::Black, CastlingSide::QueenSide) => 0b1000,
+        }
+    }
+
+    pub fn has(self, color: Color, side: CastlingSide) -> bool {
+        self.0 & CastlingRights::bit(color, side) != 0
+    }
+
+    pub fn insert(&mut self, color: Color, side: CastlingSide) {
+        self.0 |= CastlingRights::bit(color, side);
+    }
+
+    pub fn remove(&mut self, color: Color, side: CastlingSide) {
+        self.0 &= !CastlingRights::bit(color, side);
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub(crate) fn index(self) -> usize {
+        self.0 as usize
+    }
+}
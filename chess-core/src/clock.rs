@@ -0,0 +1,132 @@
+use std::{fmt, str::FromStr, time::Duration};
+
+use crate::Color;
+
+/// How much time each player gets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeControl {
+    /// All moves have to be played within the initial time.
+    SuddenDeath { initial: Duration },
+    /// `increment` is added to the clock after each move.
+    Fischer {
+        initial: Duration,
+        increment: Duration,
+    },
+    /// After each move, the time it took is given back up to `delay`.
+    Bronstein { initial: Duration, delay: Duration },
+}
+
+impl TimeControl {
+    pub fn initial(&self) -> Duration {
+        match *self {
+            TimeControl::SuddenDeath { initial }
+            | TimeControl::Fischer { initial, .. }
+            | TimeControl::Bronstein { initial, .. } => initial,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseTimeControlError(pub String);
+
+impl fmt::Display for ParseTimeControlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid time control `{}`", self.0)
+    }
+}
+
+impl std::error::Error for ParseTimeControlError {}
+
+/// Parses minutes of initial time, optionally followed by seconds of Fischer
+/// increment or Bronstein delay: `5`, `3+2` or `5d3`.
+impl FromStr for TimeControl {
+    type Err = ParseTimeControlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseTimeControlError(s.to_string());
+        let parse = |value: &str, unit: f64| {
+            value
+                .parse::<f64>()
+                .ok()
+                .and_then(|value| Duration::try_from_secs_f64(value * unit).ok())
+                .ok_or_else(invalid)
+        };
+
+        let time_control = if let Some((initial, increment)) = s.split_once('+') {
+            TimeControl::Fischer {
+                initial: parse(initial, 60.0)?,
+                increment: parse(increment, 1.0)?,
+            }
+        } else if let Some((initial, delay)) = s.split_once('d') {
+            TimeControl::Bronstein {
+                initial: parse(initial, 60.0)?,
+                delay: parse(delay, 1.0)?,
+            }
+        } else {
+            TimeControl::SuddenDeath {
+                initial: parse(s, 60.0)?,
+            }
+        };
+
+        match time_control.initial().is_zero() {
+            true => Err(invalid()),
+            false => Ok(time_control),
+        }
+    }
+}
+
+/// The clocks of both players.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChessClock {
+    time_control: TimeControl,
+    remaining: [Duration; 2],
+    /// Time spent on the move being played.
+    turn_time: Duration,
+}
+
+impl ChessClock {
+    pub fn new(time_control: TimeControl) -> ChessClock {
+        ChessClock {
+            time_control,
+            remaining: [time_control.initial(); 2],
+            turn_time: Duration::ZERO,
+        }
+    }
+
+    pub fn time_control(&self) -> TimeControl {
+        self.time_control
+    }
+
+    pub fn remaining(&self, color: Color) -> Duration {
+        self.remaining[color.index()]
+    }
+
+    /// Runs the clock of the player to move.
+    pub fn tick(&mut self, color: Color, elapsed: Duration) {
+        let remaining = &mut self.remaining[color.index()];
+        *remaining = remaining.saturating_sub(elapsed);
+        self.turn_time += elapsed;
+    }
+
+    /// Ends the turn of the player who just moved, adding any increment or
+    /// delay they are owed.
+    pub fn press(&mut self, color: Color) {
+        if self.is_flagged(color) {
+            return;
+        }
+
+        let bonus = match self.time_control {
+            TimeControl::SuddenDeath { .. } => Duration::ZERO,
+            TimeControl::Fischer { increment, .. } => increment,
+            TimeControl::Bronstein { delay, .. } => self.turn_time.min(delay),
+        };
+        let remaining = &mut self.remaining[color.index()];
+        *remaining = remaining.saturating_add(bonus);
+        self.turn_time = Duration::ZERO;
+    }
+
+    /// Whether the player ran out of time.
+    pub fn is_flagged(&self, color: Color) -> bool {
+        self.remaining(color).is_zero()
+    }
+}
@@ -0,0 +1,146 @@
+use crate::{Color, Piece, PieceType, Position, Square};
+
+/// Piece-square tables from the Simplified Evaluation Function, laid out as
+/// seen from white's side with the eighth rank first.
+#[rustfmt::skip]
+const PAWN_TABLE: [i32; 64] = [
+     0,  0,   0,   0,   0,   0,  0,  0,
+    50, 50,  50,  50,  50,  50, 50, 50,
+    10, 10,  20,  30,  30,  20, 10, 10,
+     5,  5,  10,  25,  25,  10,  5,  5,
+     0,  0,   0,  20,  20,   0,  0,  0,
+     5, -5, -10,   0,   0, -10, -5,  5,
+     5, 10,  10, -20, -20,  10, 10,  5,
+     0,  0,   0,   0,   0,   0,  0,  0,
+];
+
+#[rustfmt::skip]
+const KNIGHT_TABLE: [i32; 64] = [
+    -50, -40, -30, -30, -30, -30, -40, -50,
+    -40, -20,   0,   0,   0,   0, -20, -40,
+    -30,   0,  10,  15,  15,  10,   0, -30,
+    -30,   5,  15,  20,  20,  15,   5, -30,
+    -30,   0,  15,  20,  20,  15,   0, -30,
+    -30,   5,  10,  15,  15,  10,   5, -30,
+    -40, -20,   0,   5,   5,   0, -20, -40,
+    -50, -40, -30, -30, -30, -30, -40, -50,
+];
+
+#[rustfmt::skip]
+const BISHOP_TABLE: [i32; 64] = [
+    -20, -10, -10, -10, -10, -10, -10, -20,
+    -10,   0,   0,   0,   0,   0,   0, -10,
+    -10,   0,   5,  10,  10,   5,   0, -10,
+    -10,   5,   5,  10,  10,   5,   5, -10,
+    -10,   0,  10,  10,  10,  10,   0, -10,
+    -10,  10,  10,  10,  10,  10,  10, -10,
+    -10,   5,   0,   0,   0,   0,   5, -10,
+    -20, -10, -10, -10, -10, -10, -10, -20,
+];
+
+#[rustfmt::skip]
+const ROOK_TABLE: [i32; 64] = [
+     0,  0,  0,  0,  0,  0,  0,  0,
+     5, 10, 10, 10, 10, 10, 10,  5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+     0,  0,  0,  5,  5,  0,  0,  0,
+];
+
+#[rustfmt::skip]
+const QUEEN_TABLE: [i32; 64] = [
+    -20, -10, -10, -5, -5, -10, -10, -20,
+    -10,   0,   0,  0,  0,   0,   0, -10,
+    -10,   0,   5,  5,  5,   5,   0, -10,
+     -5,   0,   5,  5,  5,   5,   0,  -5,
+      0,   0,   5,  5,  5,   5,   0,  -5,
+    -10,   5,   5,  5,  5,   5,   0, -10,
+    -10,   0,   5,  0,  0,   0,   0, -10,
+    -20, -10, -10, -5, -5, -10, -10, -20,
+];
+
+#[rustfmt::skip]
+const KING_TABLE: [i32; 64] = [
+    -30, -40, -40, -50, -50, -40, -40, -30,
+    -30, -40, -40, -50, -50, -40, -40, -30,
+    -30, -40, -40, -50, -50, -40, -40, -30,
+    -30, -40, -40, -50, -50, -40, -40, -30,
+    -20, -30, -30, -40, -40, -30, -30, -20,
+    -10, -20, -20, -20, -20, -20, -20, -10,
+     20,  20,   0,   0,   0,   0,  20,  20,
+     20,  30,  10,   0,   0,  10,  30,  20,
+];
+
+#[rustfmt::skip]
+const KING_ENDGAME_TABLE: [i32; 64] = [
+    -50, -40, -30, -20, -20, -30, -40, -50,
+    -30, -20, -10,   0,   0, -10, -20, -30,
+    -30, -10,  20,  30,  30,  20, -10, -30,
+    -30, -10,  30,  40,  40,  30, -10, -30,
+    -30, -10,  30,  40,  40,  30, -10, -30,
+    -30, -10,  20,  30,  30,  20, -10, -30,
+    -30, -30,   0,   0,   0,   0, -30, -30,
+    -50, -30, -30, -30, -30, -30, -30, -50,
+];
+
+/// Material value of a piece in centipawns.
+pub(crate) fn piece_value(piece_type: PieceType) -> i32 {
+    match piece_type {
+        PieceType::Pawn => 100,
+        PieceType::Knight => 320,
+        PieceType::Bishop => 330,
+        PieceType::Rook => 500,
+        PieceType::Queen => 900,
+        PieceType::King => 20_000,
+    }
+}
+
+impl Position {
+    /// Static evaluation in centipawns from the point of view of the side to
+    /// move: material plus piece-square tables.
+    pub fn evaluate(&self) -> i32 {
+        // Without queens the king should head for the center
+        let endgame = !self
+            .pieces()
+            .any(|(_, piece)| piece.piece_type == PieceType::Queen);
+
+        let score: i32 = self
+            .pieces()
+            .map(|(square, piece)| {
+                let value = piece_value(piece.piece_type) + square_bonus(piece, square, endgame);
+                match piece.color {
+                    Color::White => value,
+                    Color::Black => -value,
+                }
+            })
+            .sum();
+
+        match self.side_to_move() {
+            Color::White => score,
+            Color::Black => -score,
+        }
+    }
+}
+
+fn square_bonus(piece: Piece, square: Square, endgame: bool) -> i32 {
+    let table = match piece.piece_type {
+        PieceType::Pawn => &PAWN_TABLE,
+        PieceType::Knight => &KNIGHT_TABLE,
+        PieceType::Bishop => &BISHOP_TABLE,
+        PieceType::Rook => &ROOK_TABLE,
+        PieceType::Queen => &QUEEN_TABLE,
+        PieceType::King if endgame => &KING_ENDGAME_TABLE,
+        PieceType::King => &KING_TABLE,
+    };
+
+    // Tables start at a8, so white's ranks are read from the bottom up
+    let rank = match piece.color {
+        Color::White => 7 - square.rank(),
+        Color::Black => square.rank(),
+    };
+
+    table[rank as usize * 8 + square.file() as usize]
+}
@@ -0,0 +1,261 @@
+use std::fmt;
+
+use crate::{CastlingRights, CastlingSide, Color, Piece, PieceType, Position, Square};
+
+pub const STARTING_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FenError {
+    MissingField(&'static str),
+    InvalidBoard(String),
+    InvalidSideToMove(String),
+    InvalidCastlingRights(String),
+    InvalidEnPassant(String),
+    InvalidMoveCounter(String),
+    InvalidKings,
+    /// The side that just moved left its king in check.
+    OpponentInCheck,
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FenError::MissingField(field) => write!(f, "missing {field} field"),
+            FenError::InvalidBoard(board) => write!(f, "invalid piece placement `{board}`"),
+            FenError::InvalidSideToMove(side) => write!(f, "invalid side to move `{side}`"),
+            FenError::InvalidCastlingRights(castling) => {
+                write!(f, "invalid castling rights `{castling}`")
+            }
+            FenError::InvalidEnPassant(square) => write!(f, "invalid en passant square `{square}`"),
+            FenError::InvalidMoveCounter(counter) => write!(f, "invalid move counter `{counter}`"),
+            FenError::InvalidKings => write!(f, "each side must have exactly one king"),
+            FenError::OpponentInCheck => write!(f, "the side not to move is in check"),
+        }
+    }
+}
+
+impl std::error::Error for FenError {}
+
+impl Position {
+    /// Parses a position in Forsyth-Edwards Notation. The move counters are
+    /// optional and default to `0 1`.
+    pub fn from_fen(fen: &str) -> Result<Position, FenError> {
+        let mut fields = fen.split_whitespace();
+        let mut position = Position::empty();
+
+        let board = fields
+            .next()
+            .ok_or(FenError::MissingField("piece placement"))?;
+        parse_board(&mut position, board)?;
+
+        position.set_side_to_move(match fields.next() {
+            Some("w") => Color::White,
+            Some("b") => Color::Black,
+            Some(side) => return Err(FenError::InvalidSideToMove(side.to_string())),
+            None => return Err(FenError::MissingField("side to move")),
+        });
+
+        let castling = fields
+            .next()
+            .ok_or(FenError::MissingField("castling rights"))?;
+        let mut castling_rights = parse_castling_rights(castling)?;
+
+        // Drop rights for which the king or rook is not on its starting square
+        for color in [Color::White, Color::Black] {
+            for side in [CastlingSide::KingSide, CastlingSide::QueenSide] {
+                let rank = color.back_rank();
+                let king = position.piece_at(Square::new(4, rank));
+                let rook = position.piece_at(Square::new(side.rook_file(), rank));
+
+                if king != Some(Piece::new(PieceType::King, color))
+                    || rook != Some(Piece::new(PieceType::Rook, color))
+                {
+                    castling_rights.remove(color, side);
+                }
+            }
+        }
+        position.set_castling_rights(castling_rights);
+
+        position.set_en_passant(match fields.next() {
+            Some("-") => None,
+            Some(square) => Some(
+                square
+                    .parse::<Square>()
+                    .ok()
+                    .filter(|&square| is_en_passant_square(&position, square))
+                    .ok_or_else(|| FenError::InvalidEnPassant(square.to_string()))?,
+            ),
+            None => return Err(FenError::MissingField("en passant")),
+        });
+
+        let parse_counter = |counter: &str| {
+            counter
+                .parse::<u32>()
+                .map_err(|_| FenError::InvalidMoveCounter(counter.to_string()))
+        };
+        position.set_halfmove_clock(fields.next().map_or(Ok(0), parse_counter)?);
+        position.set_fullmove_number(fields.next().map_or(Ok(1), parse_counter)?.max(1));
+
+        for color in [Color::White, Color::Black] {
+            let kings = position
+                .pieces()
+                .filter(|(_, piece)| *piece == Piece::new(PieceType::King, color))
+                .count();
+
+            if kings != 1 {
+                return Err(FenError::InvalidKings);
+            }
+        }
+
+        let opponent = position.side_to_move().opposite();
+        if position
+            .king_square(opponent)
+            .is_some_and(|king| position.is_square_attacked(king, position.side_to_move()))
+        {
+            return Err(FenError::OpponentInCheck);
+        }
+
+        Ok(position)
+    }
+
+    /// Formats the position in Forsyth-Edwards Notation.
+    pub fn to_fen(&self) -> String {
+        let mut fen = String::new();
+
+        for rank in (0..8).rev() {
+            let mut empty_squares = 0;
+            for file in 0..8 {
+                match self.piece_at(Square::new(file, rank)) {
+                    Some(piece) => {
+                        if empty_squares > 0 {
+                            fen.push_str(&empty_squares.to_string());
+                            empty_squares = 0;
+                        }
+                        fen.push(piece.to_fen_char());
+                    }
+                    None => empty_squares += 1,
+                }
+            }
+
+            if empty_squares > 0 {
+                fen.push_str(&empty_squares.to_string());
+            }
+            if rank > 0 {
+                fen.push('/');
+            }
+        }
+
+        fen.push(' ');
+        fen.push(match self.side_to_move() {
+            Color::White => 'w',
+            Color::Black => 'b',
+        });
+
+        fen.push(' ');
+        let castling_rights = self.castling_rights();
+        if castling_rights.is_empty() {
+            fen.push('-');
+        }
+        for color in [Color::White, Color::Black] {
+            for side in [CastlingSide::KingSide, CastlingSide::QueenSide] {
+                if castling_rights.has(color, side) {
+                    let piece_type = match side {
+                        CastlingSide::KingSide => PieceType::King,
+                        CastlingSide::QueenSide => PieceType::Queen,
+                    };
+                    fen.push(Piece::new(piece_type, color).to_fen_char());
+                }
+            }
+        }
+
+        let en_passant = self
+            .en_passant()
+            .map_or("-".to_string(), |square| square.to_string());
+
+        format!(
+            "{fen} {en_passant} {} {}",
+            self.halfmove_clock(),
+            self.fullmove_number()
+        )
+    }
+}
+
+fn parse_board(position: &mut Position, board: &str) -> Result<(), FenError> {
+    let invalid_board = || FenError::InvalidBoard(board.to_string());
+
+    let ranks: Vec<&str> = board.split('/').collect();
+    if ranks.len() != 8 {
+        return Err(invalid_board());
+    }
+
+    // Ranks are listed from the eighth down to the first
+    for (rank, row) in (0..8).rev().zip(ranks) {
+        let mut file = 0;
+        for c in row.chars() {
+            match c.to_digit(10) {
+                Some(empty_squares @ 1..=8) => {
+                    // Checked before adding, so a long run of digits cannot
+                    // overflow
+                    if file + empty_squares as u8 > 8 {
+                        return Err(invalid_board());
+                    }
+                    file += empty_squares as u8;
+                }
+                _ => {
+                    let piece = Piece::from_fen_char(c).ok_or_else(invalid_board)?;
+                    if file >= 8 {
+                        return Err(invalid_board());
+                    }
+                    position.set_piece(Square::new(file, rank), Some(piece));
+                    file += 1;
+                }
+            }
+        }
+
+        if file != 8 {
+            return Err(invalid_board());
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether `square` was skipped by a pawn the opponent just pushed two
+/// squares: the pawn is in front of it, and it and the square the pawn came
+/// from are empty.
+fn is_en_passant_square(position: &Position, square: Square) -> bool {
+    let opponent = position.side_to_move().opposite();
+    let forward = opponent.forward();
+    if square.rank() as i32 != opponent.back_rank() as i32 + 2 * forward {
+        return false;
+    }
+
+    let pawn = square.offset(0, forward);
+    let start = square.offset(0, -forward);
+    position.piece_at(square).is_none()
+        && start.is_some_and(|start| position.piece_at(start).is_none())
+        && pawn.and_then(|pawn| position.piece_at(pawn))
+            == Some(Piece::new(PieceType::Pawn, opponent))
+}
+
+fn parse_castling_rights(castling: &str) -> Result<CastlingRights, FenError> {
+    let mut castling_rights = CastlingRights::NONE;
+    if castling == "-" {
+        return Ok(castling_rights);
+    }
+
+    for c in castling.chars() {
+        let color = match c.is_ascii_uppercase() {
+            true => Color::White,
+            false => Color::Black,
+        };
+        let side = match c.to_ascii_uppercase() {
+            'K' => CastlingSide::KingSide,
+            'Q' => CastlingSide::QueenSide,
+            _ => return Err(FenError::InvalidCastlingRights(castling.to_string())),
+        };
+        castling_rights.insert(color, side);
+    }
+
+    Ok(castling_rights)
+}
@@ -0,0 +1,35 @@
+//! Chess rules independent of any rendering or game engine: board
+//! representation, move generation, game outcome detection and a search
+//! for the best move.
+
+mod bitboard;
+mod castling;
+mod clock;
+mod color;
+mod eval;
+mod fen;
+mod moves;
+pub mod net;
+mod perft;
+mod pgn;
+mod piece;
+mod pieces;
+mod position;
+mod san;
+mod search;
+mod square;
+pub mod uci;
+mod zobrist;
+
+pub use bitboard::Bitboard;
+pub use castling::{CastlingRights, CastlingSide};
+pub use clock::{ChessClock, ParseTimeControlError, TimeControl};
+pub use color::Color;
+pub use fen::{FenError, STARTING_FEN};
+pub use moves::{Move, ParseMoveError};
+pub use pgn::{GameResult, Pgn, PgnError};
+pub use piece::{Piece, PieceType};
+pub use position::{DrawReason, Outcome, Position};
+pub use san::SanError;
+pub use search::{search, SearchLimits, SearchResult, MATE_SCORE};
+pub use square::{ParseSquareError, Square};
@@ -0,0 +1,80 @@
+use std::{fmt, str::FromStr};
+
+use crate::{PieceType, Square};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Move {
+    pub from: Square,
+    pub to: Square,
+    pub promotion: Option<PieceType>,
+}
+
+impl Move {
+    pub fn new(from: Square, to: Square) -> Move {
+        Move {
+            from,
+            to,
+            promotion: None,
+        }
+    }
+
+    pub fn with_promotion(from: Square, to: Square, promotion: PieceType) -> Move {
+        Move {
+            from,
+            to,
+            promotion: Some(promotion),
+        }
+    }
+}
+
+/// Formats the move in coordinate notation, e.g. `e2e4` or `e7e8q`.
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.from, self.to)?;
+
+        match self.promotion {
+            Some(promotion) => write!(f, "{}", promotion.to_char().to_ascii_lowercase()),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseMoveError(pub String);
+
+impl fmt::Display for ParseMoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid move `{}`", self.0)
+    }
+}
+
+impl std::error::Error for ParseMoveError {}
+
+/// Parses a move in coordinate notation, e.g. `e2e4` or `e7e8q`.
+impl FromStr for Move {
+    type Err = ParseMoveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseMoveError(s.to_string());
+
+        if !s.is_ascii() || !(4..=5).contains(&s.len()) {
+            return Err(invalid());
+        }
+
+        let from = s[0..2].parse().map_err(|_| invalid())?;
+        let to = s[2..4].parse().map_err(|_| invalid())?;
+        let promotion = match s[4..].chars().next() {
+            Some(c) => match PieceType::from_char(c) {
+                Some(PieceType::King | PieceType::Pawn) | None => return Err(invalid()),
+                promotion => promotion,
+            },
+            None => None,
+        };
+
+        Ok(Move {
+            from,
+            to,
+            promotion,
+        })
+    }
+}
@@ -0,0 +1,207 @@
+//! A line based protocol for two players on separate machines. The host
+//! sends `hello <version> <color> <fen>`, telling the joining player which
+//! color it plays from which position, then both sides send `move <uci>` for
+//! each move they play and `bye` when leaving.
+
+use std::{
+    fmt,
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    str::FromStr,
+};
+
+use crate::{Color, FenError, Move, Position};
+
+pub const PROTOCOL_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Message {
+    Hello {
+        version: u32,
+        color: Color,
+        fen: String,
+    },
+    Move(Move),
+    Bye,
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Message::Hello {
+                version,
+                color,
+                fen,
+            } => write!(f, "hello {version} {color} {fen}"),
+            Message::Move(mv) => write!(f, "move {mv}"),
+            Message::Bye => write!(f, "bye"),
+        }
+    }
+}
+
+impl FromStr for Message {
+    type Err = NetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || NetError::InvalidMessage(s.to_string());
+        let mut tokens = s.split_whitespace();
+
+        match tokens.next() {
+            Some("hello") => {
+                let version = tokens.next().and_then(|version| version.parse().ok());
+                let color = match tokens.next() {
+                    Some("white") => Color::White,
+                    Some("black") => Color::Black,
+                    _ => return Err(invalid()),
+                };
+                let fen = tokens.collect::<Vec<_>>().join(" ");
+
+                Ok(Message::Hello {
+                    version: version.ok_or_else(invalid)?,
+                    color,
+                    fen,
+                })
+            }
+            Some("move") => {
+                let mv = tokens.next().and_then(|mv| mv.parse().ok());
+                Ok(Message::Move(mv.ok_or_else(invalid)?))
+            }
+            Some("bye") => Ok(Message::Bye),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum NetError {
+    Io(io::Error),
+    Disconnected,
+    InvalidMessage(String),
+    UnsupportedVersion(u32),
+    InvalidFen(FenError),
+    OutOfTurn(Move),
+    IllegalMove(Move),
+}
+
+impl fmt::Display for NetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetError::Io(err) => write!(f, "connection error: {err}"),
+            NetError::Disconnected => write!(f, "the other player disconnected"),
+            NetError::InvalidMessage(message) => write!(f, "invalid message `{message}`"),
+            NetError::UnsupportedVersion(version) => {
+                write!(f, "unsupported protocol version {version}")
+            }
+            NetError::InvalidFen(err) => write!(f, "invalid starting position: {err}"),
+            NetError::OutOfTurn(mv) => write!(f, "move `{mv}` played out of turn"),
+            NetError::IllegalMove(mv) => write!(f, "illegal move `{mv}`"),
+        }
+    }
+}
+
+impl std::error::Error for NetError {}
+
+impl From<io::Error> for NetError {
+    fn from(err: io::Error) -> Self {
+        NetError::Io(err)
+    }
+}
+
+/// One end of a game between two machines.
+pub struct Peer {
+    writer: TcpStream,
+    reader: BufReader<TcpStream>,
+    /// Color played on this end.
+    pub color: Color,
+    /// Position the game starts from, agreed on when connecting.
+    pub start_position: Position,
+}
+
+impl Peer {
+    /// Waits for a player to join and tells it to play the other color from
+    /// `start_position`.
+    pub fn host(
+        listener: &TcpListener,
+        start_position: &Position,
+        color: Color,
+    ) -> Result<Peer, NetError> {
+        let (stream, _) = listener.accept()?;
+        let mut peer = Peer::new(stream, color, start_position.clone())?;
+
+        peer.send(&Message::Hello {
+            version: PROTOCOL_VERSION,
+            color: color.opposite(),
+            fen: start_position.to_fen(),
+        })?;
+
+        Ok(peer)
+    }
+
+    /// Connects to a host and learns the color and position to play from.
+    pub fn join(address: impl ToSocketAddrs) -> Result<Peer, NetError> {
+        let stream = TcpStream::connect(address)?;
+        let mut peer = Peer::new(stream, Color::White, Position::new())?;
+
+        match peer.receive()? {
+            Message::Hello { version, .. } if version != PROTOCOL_VERSION => {
+                Err(NetError::UnsupportedVersion(version))
+            }
+            Message::Hello { color, fen, .. } => {
+                peer.color = color;
+                peer.start_position = Position::from_fen(&fen).map_err(NetError::InvalidFen)?;
+                Ok(peer)
+            }
+            message => Err(NetError::InvalidMessage(message.to_string())),
+        }
+    }
+
+    fn new(stream: TcpStream, color: Color, start_position: Position) -> Result<Peer, NetError> {
+        stream.set_nodelay(true)?;
+
+        Ok(Peer {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+            color,
+            start_position,
+        })
+    }
+
+    /// Another handle on the same connection, e.g. to receive on a separate
+    /// thread while sending from this one.
+    pub fn try_clone(&self) -> Result<Peer, NetError> {
+        Peer::new(
+            self.writer.try_clone()?,
+            self.color,
+            self.start_position.clone(),
+        )
+    }
+
+    pub fn send(&mut self, message: &Message) -> Result<(), NetError> {
+        writeln!(self.writer, "{message}")?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Waits for the next message from the other player.
+    pub fn receive(&mut self) -> Result<Message, NetError> {
+        let mut line = String::new();
+        match self.reader.read_line(&mut line)? {
+            0 => Err(NetError::Disconnected),
+            _ => line.trim().parse(),
+        }
+    }
+
+    /// Plays a move received from the other player, once it is checked to be
+    /// their turn and the move to be legal.
+    pub fn apply_remote(&self, position: &mut Position, mv: Move) -> Result<(), NetError> {
+        if position.side_to_move() == self.color {
+            return Err(NetError::OutOfTurn(mv));
+        }
+        if !position.is_legal(mv) {
+            return Err(NetError::IllegalMove(mv));
+        }
+
+        position.make_move(mv);
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{Move, Position};
+
+impl Position {
+    /// Counts the leaf nodes of the legal move tree to the given depth, to
+    /// check move generation against known results.
+    pub fn perft(&self, depth: u32) -> u64 {
+        let mut position = self.without_history();
+        perft(&mut position, depth)
+    }
+
+    /// Perft count below each legal move, handy to narrow down which move
+    /// generation differs from a reference.
+    pub fn perft_divide(&self, depth: u32) -> Vec<(Move, u64)> {
+        let mut position = self.without_history();
+
+        self.legal_moves()
+            .into_iter()
+            .map(|mv| {
+                position.make_move(mv);
+                let nodes = perft(&mut position, depth.saturating_sub(1));
+                position.unmake_move();
+                (mv, nodes)
+            })
+            .collect()
+    }
+}
+
+fn perft(position: &mut Position, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+
+    let moves = position.legal_moves();
+    if depth == 1 {
+        return moves.len() as u64;
+    }
+
+    moves
+        .into_iter()
+        .map(|mv| {
+            position.make_move(mv);
+            let nodes = perft(position, depth - 1);
+            position.unmake_move();
+            nodes
+        })
+        .sum()
+}
@@ -0,0 +1,339 @@
+use std::{fmt, str::FromStr};
+
+use crate::{Color, FenError, Move, Outcome, Position, SanError, STARTING_FEN};
+
+/// Result of a game as written in PGN.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameResult {
+    WhiteWins,
+    BlackWins,
+    Draw,
+    Ongoing,
+}
+
+impl GameResult {
+    pub fn from_outcome(outcome: Option<Outcome>) -> GameResult {
+        match outcome {
+            Some(Outcome::Checkmate {
+                winner: Color::White,
+            }) => GameResult::WhiteWins,
+            Some(Outcome::Checkmate {
+                winner: Color::Black,
+            }) => GameResult::BlackWins,
+            Some(Outcome::Draw(_)) => GameResult::Draw,
+            None => GameResult::Ongoing,
+        }
+    }
+}
+
+impl FromStr for GameResult {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1-0" => Ok(GameResult::WhiteWins),
+            "0-1" => Ok(GameResult::BlackWins),
+            "1/2-1/2" => Ok(GameResult::Draw),
+            "*" => Ok(GameResult::Ongoing),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameResult::WhiteWins => write!(f, "1-0"),
+            GameResult::BlackWins => write!(f, "0-1"),
+            GameResult::Draw => write!(f, "1/2-1/2"),
+            GameResult::Ongoing => write!(f, "*"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PgnError {
+    InvalidTag(String),
+    UnterminatedComment,
+    UnterminatedVariation,
+    /// A `)` without a variation to close.
+    UnmatchedParenthesis,
+    InvalidFen(FenError),
+    InvalidMove {
+        move_number: u32,
+        color: Color,
+        error: SanError,
+    },
+}
+
+impl fmt::Display for PgnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PgnError::InvalidTag(tag) => write!(f, "invalid tag pair `{tag}`"),
+            PgnError::UnterminatedComment => write!(f, "unterminated comment"),
+            PgnError::UnterminatedVariation => write!(f, "unterminated variation"),
+            PgnError::UnmatchedParenthesis => write!(f, "unmatched `)`"),
+            PgnError::InvalidFen(err) => write!(f, "invalid FEN tag: {err}"),
+            PgnError::InvalidMove {
+                move_number,
+                color: Color::White,
+                error,
+            } => write!(f, "move {move_number}.: {error}"),
+            PgnError::InvalidMove {
+                move_number,
+                color: Color::Black,
+                error,
+            } => write!(f, "move {move_number}...: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for PgnError {}
+
+/// A game in Portable Game Notation: tag pairs, the position it started
+/// from and the moves played.
+#[derive(Clone, Debug)]
+pub struct Pgn {
+    pub tags: Vec<(String, String)>,
+    pub start_position: Position,
+    pub moves: Vec<Move>,
+    pub result: GameResult,
+}
+
+impl Pgn {
+    /// Builds the PGN of the moves played to reach `position`, with the
+    /// Seven Tag Roster set to unknown values.
+    pub fn from_position(position: &Position, result: GameResult) -> Pgn {
+        let tags = [
+            ("Event", "?"),
+            ("Site", "?"),
+            ("Date", "????.??.??"),
+            ("Round", "?"),
+            ("White", "?"),
+            ("Black", "?"),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+
+        Pgn {
+            tags,
+            start_position: position.start_position(),
+            moves: position.moves().collect(),
+            result,
+        }
+    }
+
+    /// The position reached after playing every move of the game.
+    pub fn end_position(&self) -> Position {
+        let mut position = self.start_position.clone();
+        for mv in &self.moves {
+            position.make_move(*mv);
+        }
+        position
+    }
+
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag, _)| tag == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        match self.tags.iter_mut().find(|(tag, _)| tag == name) {
+            Some((_, old_value)) => *old_value = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Pgn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, value) in &self.tags {
+            if name != "Result" && name != "SetUp" && name != "FEN" {
+                writeln!(f, "[{name} \"{}\"]", escape(value))?;
+            }
+        }
+        writeln!(f, "[Result \"{}\"]", self.result)?;
+
+        let start_fen = self.start_position.to_fen();
+        if start_fen != STARTING_FEN {
+            writeln!(f, "[SetUp \"1\"]")?;
+            writeln!(f, "[FEN \"{start_fen}\"]")?;
+        }
+        writeln!(f)?;
+
+        let mut position = self.start_position.clone();
+        let mut tokens = vec![];
+        for (i, mv) in self.moves.iter().enumerate() {
+            let move_number = position.fullmove_number();
+            match position.side_to_move() {
+                Color::White => tokens.push(format!("{move_number}.")),
+                Color::Black if i == 0 => tokens.push(format!("{move_number}...")),
+                Color::Black => {}
+            }
+
+            tokens.push(position.to_san(*mv));
+            position.make_move(*mv);
+        }
+        tokens.push(self.result.to_string());
+
+        // Keep movetext lines under 80 characters
+        let mut line_length = 0;
+        for token in tokens {
+            if line_length > 0 && line_length + token.len() + 1 > 79 {
+                writeln!(f)?;
+                line_length = 0;
+            } else if line_length > 0 {
+                write!(f, " ")?;
+                line_length += 1;
+            }
+            write!(f, "{token}")?;
+            line_length += token.len();
+        }
+        writeln!(f)
+    }
+}
+
+/// Parses the first game of a PGN text. Comments, variations and numeric
+/// annotation glyphs are skipped.
+impl FromStr for Pgn {
+    type Err = PgnError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize(s)?;
+
+        // The tag pair section ends where the movetext starts
+        let tags: Vec<(String, String)> = tokens
+            .iter()
+            .map_while(|token| match token {
+                Token::Tag(name, value) => Some((name.clone(), value.clone())),
+                Token::Symbol(_) => None,
+            })
+            .collect();
+
+        let fen = tags
+            .iter()
+            .find(|(name, _)| name == "FEN")
+            .map(|(_, fen)| fen.as_str());
+        let start_position = match fen {
+            Some(fen) => Position::from_fen(fen).map_err(PgnError::InvalidFen)?,
+            None => Position::new(),
+        };
+
+        let mut result = tags
+            .iter()
+            .find(|(name, _)| name == "Result")
+            .and_then(|(_, result)| result.parse().ok())
+            .unwrap_or(GameResult::Ongoing);
+
+        let mut position = start_position.clone();
+        let mut moves = vec![];
+        for token in &tokens[tags.len()..] {
+            let Token::Symbol(symbol) = token else {
+                break;
+            };
+
+            if let Ok(game_result) = symbol.parse() {
+                result = game_result;
+                break;
+            }
+
+            // Move numbers may be glued to the move, as in `1.e4`
+            let san = match symbol.starts_with(|c: char| c.is_ascii_digit()) {
+                true => symbol.rsplit('.').next().unwrap_or_default(),
+                false => symbol.as_str(),
+            };
+            if san.is_empty() || san.starts_with('$') {
+                continue;
+            }
+
+            let mv = position
+                .parse_san(san)
+                .map_err(|error| PgnError::InvalidMove {
+                    move_number: position.fullmove_number(),
+                    color: position.side_to_move(),
+                    error,
+                })?;
+            position.make_move(mv);
+            moves.push(mv);
+        }
+
+        Ok(Pgn {
+            tags,
+            start_position,
+            moves,
+            result,
+        })
+    }
+}
+
+enum Token {
+    Tag(String, String),
+    Symbol(String),
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, PgnError> {
+    let mut tokens = vec![];
+    let mut variation_depth = 0;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '[' => {
+                let tag: String = chars.by_ref().take_while(|c| *c != ']').collect();
+                let token = parse_tag(&tag).ok_or(PgnError::InvalidTag(tag))?;
+                tokens.push(token);
+            }
+            '{' => {
+                if !chars.by_ref().any(|c| c == '}') {
+                    return Err(PgnError::UnterminatedComment);
+                }
+            }
+            ';' => while chars.next_if(|c| *c != '\n').is_some() {},
+            '(' => variation_depth += 1,
+            ')' if variation_depth == 0 => return Err(PgnError::UnmatchedParenthesis),
+            ')' => variation_depth -= 1,
+            c if c.is_whitespace() => {}
+            c => {
+                let mut symbol = c.to_string();
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace() && !"[]{}();".contains(*c))
+                {
+                    symbol.push(c);
+                }
+
+                if variation_depth == 0 {
+                    tokens.push(Token::Symbol(symbol));
+                }
+            }
+        }
+    }
+
+    if variation_depth > 0 {
+        return Err(PgnError::UnterminatedVariation);
+    }
+
+    Ok(tokens)
+}
+
+/// Parses the inside of a `[Name "value"]` tag pair.
+fn parse_tag(tag: &str) -> Option<Token> {
+    let (name, value) = tag.trim().split_once(char::is_whitespace)?;
+    let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+
+    let mut unescaped = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unescaped.push(chars.next()?),
+            c => unescaped.push(c),
+        }
+    }
+
+    Some(Token::Tag(name.to_string(), unescaped))
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
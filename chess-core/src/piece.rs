@@ -0,0 +1,79 @@
+use crate::Color;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PieceType {
+    King,
+    Queen,
+    Bishop,
+    Knight,
+    Rook,
+    Pawn,
+}
+
+impl PieceType {
+    /// Piece types a pawn can be promoted to.
+    pub const PROMOTIONS: [PieceType; 4] = [
+        PieceType::Queen,
+        PieceType::Knight,
+        PieceType::Rook,
+        PieceType::Bishop,
+    ];
+
+    pub(crate) fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Uppercase letter of the piece type, as used in FEN and SAN.
+    pub fn to_char(self) -> char {
+        match self {
+            PieceType::King => 'K',
+            PieceType::Queen => 'Q',
+            PieceType::Bishop => 'B',
+            PieceType::Knight => 'N',
+            PieceType::Rook => 'R',
+            PieceType::Pawn => 'P',
+        }
+    }
+
+    /// Parses a piece letter in either case.
+    pub fn from_char(c: char) -> Option<PieceType> {
+        match c.to_ascii_uppercase() {
+            'K' => Some(PieceType::King),
+            'Q' => Some(PieceType::Queen),
+            'B' => Some(PieceType::Bishop),
+            'N' => Some(PieceType::Knight),
+            'R' => Some(PieceType::Rook),
+            'P' => Some(PieceType::Pawn),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Piece {
+    pub piece_type: PieceType,
+    pub color: Color,
+}
+
+impl Piece {
+    pub fn new(piece_type: PieceType, color: Color) -> Piece {
+        Piece { piece_type, color }
+    }
+
+    /// FEN letter of the piece, uppercase for white and lowercase for black.
+    pub fn to_fen_char(self) -> char {
+        match self.color {
+            Color::White => self.piece_type.to_char(),
+            Color::Black => self.piece_type.to_char().to_ascii_lowercase(),
+        }
+    }
+
+    pub fn from_fen_char(c: char) -> Option<Piece> {
+        let color = match c.is_ascii_uppercase() {
+            true => Color::White,
+            false => Color::Black,
+        };
+
+        PieceType::from_char(c).map(|piece_type| Piece::new(piece_type, color))
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{Bitboard, Color, Square};
+
+const KNIGHT_OFFSETS: [(i32, i32); 8] = [
+    (-2, 1),
+    (-1, 2),
+    (1, 2),
+    (2, 1),
+    (2, -1),
+    (1, -2),
+    (-1, -2),
+    (-2, -1),
+];
+
+const KING_OFFSETS: [(i32, i32); 8] = [
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+];
+
+/// Sliding directions, those towards higher square indices first.
+const DIRECTIONS: [(i32, i32); 8] = [
+    (0, 1),
+    (1, 0),
+    (1, 1),
+    (-1, 1),
+    (0, -1),
+    (-1, 0),
+    (-1, -1),
+    (1, -1),
+];
+
+const ROOK_DIRECTIONS: [usize; 4] = [0, 1, 4, 5];
+const BISHOP_DIRECTIONS: [usize; 4] = [2, 3, 6, 7];
+
+const KNIGHT_ATTACKS: [u64; 64] = offset_table(&KNIGHT_OFFSETS);
+const KING_ATTACKS: [u64; 64] = offset_table(&KING_OFFSETS);
+const PAWN_ATTACKS: [[u64; 64]; 2] = [
+    offset_table(&[(-1, 1), (1, 1)]),
+    offset_table(&[(-1, -1), (1, -1)]),
+];
+
+/// Squares from each square to the edge of the board in each direction,
+/// excluding the square itself.
+const RAYS: [[u64; 64]; 8] = {
+    let mut rays = [[0; 64]; 8];
+    let mut direction = 0;
+    while direction < 8 {
+        let (file_step, rank_step) = DIRECTIONS[direction];
+        let mut square = 0;
+        while square < 64 {
+            let mut file = (square % 8) as i32 + file_step;
+            let mut rank = (square / 8) as i32 + rank_step;
+            while file >= 0 && file < 8 && rank >= 0 && rank < 8 {
+                rays[direction][square] |= 1 << (rank * 8 + file);
+                file += file_step;
+                rank += rank_step;
+            }
+            square += 1;
+        }
+        direction += 1;
+    }
+    rays
+};
+
+/// Squares reached from each square by jumping by the given offsets.
+const fn offset_table(offsets: &[(i32, i32)]) -> [u64; 64] {
+    let mut table = [0; 64];
+    let mut square = 0;
+    while square < 64 {
+        let mut i = 0;
+        while i < offsets.len() {
+            let file = (square % 8) as i32 + offsets[i].0;
+            let rank = (square / 8) as i32 + offsets[i].1;
+            if file >= 0 && file < 8 && rank >= 0 && rank < 8 {
+                table[square] |= 1 << (rank * 8 + file);
+            }
+            i += 1;
+        }
+        square += 1;
+    }
+    table
+}
+
+pub(crate) fn knight_attacks(square: Square) -> Bitboard {
+    Bitboard(KNIGHT_ATTACKS[square.index()])
+}
+
+pub(crate) fn king_attacks(square: Square) -> Bitboard {
+    Bitboard(KING_ATTACKS[square.index()])
+}
+
+/// Squares a pawn of the given color on `square` captures on.
+pub(crate) fn pawn_attacks(color: Color, square: Square) -> Bitboard {
+    Bitboard(PAWN_ATTACKS[color.index()][square.index()])
+}
+
+pub(crate) fn rook_attacks(square: Square, occupied: Bitboard) -> Bitboard {
+    ROOK_DIRECTIONS
+        .into_iter()
+        .fold(Bitboard::EMPTY, |attacks, direction| {
+            attacks | ray_attacks(direction, square, occupied)
+        })
+}
+
+pub(crate) fn bishop_attacks(square: Square, occupied: Bitboard) -> Bitboard {
+    BISHOP_DIRECTIONS
+        .into_iter()
+        .fold(Bitboard::EMPTY, |attacks, direction| {
+            attacks | ray_attacks(direction, square, occupied)
+        })
+}
+
+pub(crate) fn queen_attacks(square: Square, occupied: Bitboard) -> Bitboard {
+    rook_attacks(square, occupied) | bishop_attacks(square, occupied)
+}
+
+/// Squares along a ray up to and including the first occupied one.
+fn ray_attacks(direction: usize, square: Square, occupied: Bitboard) -> Bitboard {
+    let ray = Bitboard(RAYS[direction][square.index()]);
+
+    // The nearest blocker has the lowest index on rays going up the board
+    // and the highest on rays going down
+    let blocker = match direction < 4 {
+        true => (ray & occupied).first(),
+        false => (ray & occupied).last(),
+    };
+
+    match blocker {
+        Some(blocker) => ray ^ Bitboard(RAYS[direction][blocker.index()]),
+        None => ray,
+    }
+}
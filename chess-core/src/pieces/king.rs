@@ -0,0 +1,36 @@
+use super::is_square_attacked;
+use crate::{CastlingSide, Color, Move, Position, Square};
+
+pub(super) fn add_castling_moves(
+    position: &Position,
+    from: Square,
+    color: Color,
+    moves: &mut Vec<Move>,
+) {
+    let rank = color.back_rank();
+    let opponent = color.opposite();
+
+    if from != Square::new(4, rank) || is_square_attacked(position, from, opponent) {
+        return;
+    }
+
+    for side in [CastlingSide::KingSide, CastlingSide::QueenSide] {
+        if !position.castling_rights().has(color, side) {
+            continue;
+        }
+
+        let rook_file = side.rook_file();
+        let king_target_file = side.king_target_file();
+
+        let path_is_empty = (rook_file.min(4) + 1..rook_file.max(4))
+            .all(|file| position.piece_at(Square::new(file, rank)).is_none());
+
+        // The king may not pass through or land on an attacked tile
+        let path_is_safe = (king_target_file.min(4)..=king_target_file.max(4))
+            .all(|file| !is_square_attacked(position, Square::new(file, rank), opponent));
+
+        if path_is_empty && path_is_safe {
+            moves.push(Move::new(from, Square::new(king_target_file, rank)));
+        }
+    }
+}
@@ -0,0 +1,56 @@
+pub(crate) mod attacks;
+mod king;
+mod pawn;
+
+use crate::{Bitboard, Color, Move, Piece, PieceType, Position, Square};
+
+use attacks::{
+    bishop_attacks, king_attacks, knight_attacks, pawn_attacks, queen_attacks, rook_attacks,
+};
+
+/// Moves the piece on `from` can make, without checking whether they leave
+/// its own king in check.
+pub(crate) fn get_valid_moves(
+    position: &Position,
+    from: Square,
+    piece: Piece,
+    moves: &mut Vec<Move>,
+) {
+    let occupied = position.occupied();
+    let targets = match piece.piece_type {
+        PieceType::King => king_attacks(from),
+        PieceType::Queen => queen_attacks(from, occupied),
+        PieceType::Bishop => bishop_attacks(from, occupied),
+        PieceType::Knight => knight_attacks(from),
+        PieceType::Rook => rook_attacks(from, occupied),
+        PieceType::Pawn => return pawn::get_valid_moves(position, from, piece.color, moves),
+    };
+
+    let targets = targets & !position.occupied_by(piece.color);
+    moves.extend(targets.map(|to| Move::new(from, to)));
+
+    if piece.piece_type == PieceType::King {
+        king::add_castling_moves(position, from, piece.color, moves);
+    }
+}
+
+/// Whether `by_color` attacks `square`, found by looking from the square
+/// outwards as if it held each kind of piece.
+pub(crate) fn is_square_attacked(position: &Position, square: Square, by_color: Color) -> bool {
+    let occupied = position.occupied();
+    let attackers = |piece_types: &[PieceType]| {
+        piece_types
+            .iter()
+            .fold(Bitboard::EMPTY, |attackers, piece_type| {
+                attackers | position.bitboard(Piece::new(*piece_type, by_color))
+            })
+    };
+
+    let attacks = (pawn_attacks(by_color.opposite(), square) & attackers(&[PieceType::Pawn]))
+        | (knight_attacks(square) & attackers(&[PieceType::Knight]))
+        | (king_attacks(square) & attackers(&[PieceType::King]))
+        | (rook_attacks(square, occupied) & attackers(&[PieceType::Rook, PieceType::Queen]))
+        | (bishop_attacks(square, occupied) & attackers(&[PieceType::Bishop, PieceType::Queen]));
+
+    !attacks.is_empty()
+}
@@ -0,0 +1,49 @@
+use super::attacks::pawn_attacks;
+use crate::{Bitboard, Color, Move, PieceType, Position, Square};
+
+pub(super) fn get_valid_moves(
+    position: &Position,
+    from: Square,
+    color: Color,
+    moves: &mut Vec<Move>,
+) {
+    let forward = color.forward();
+
+    // Check if it can advance
+    if let Some(new_square) = from.offset(0, forward) {
+        if position.piece_at(new_square).is_none() {
+            add_pawn_move(from, new_square, moves);
+
+            // Check if it can advance an extra tile if first pawn move
+            let start_rank = (color.back_rank() as i32 + forward) as u8;
+            if from.rank() == start_rank {
+                if let Some(new_square) = new_square.offset(0, forward) {
+                    if position.piece_at(new_square).is_none() {
+                        moves.push(Move::new(from, new_square));
+                    }
+                }
+            }
+        }
+    }
+
+    // Check if it can capture a piece, including en passant
+    let mut capturable = position.occupied_by(color.opposite());
+    if let Some(en_passant) = position.en_passant() {
+        capturable |= Bitboard::from_square(en_passant);
+    }
+    for new_square in pawn_attacks(color, from) & capturable {
+        add_pawn_move(from, new_square, moves);
+    }
+}
+
+/// Adds a pawn move, expanded into every promotion choice on the last rank.
+fn add_pawn_move(from: Square, to: Square, moves: &mut Vec<Move>) {
+    match to.rank() == 0 || to.rank() == 7 {
+        true => moves.extend(
+            PieceType::PROMOTIONS
+                .into_iter()
+                .map(|promotion| Move::with_promotion(from, to, promotion)),
+        ),
+        false => moves.push(Move::new(from, to)),
+    }
+}
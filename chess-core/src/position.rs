@@ -0,0 +1,467 @@
+use crate::{
+    fen::STARTING_FEN,
+    pieces::{attacks::pawn_attacks, get_valid_moves, is_square_attacked},
+    zobrist, Bitboard, CastlingRights, CastlingSide, Color, Move, Piece, PieceType, Square,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DrawReason {
+    Stalemate,
+    /// Neither side has the material left to mate.
+    InsufficientMaterial,
+    /// Claimed after fifty moves by each side without a capture or pawn move.
+    FiftyMoveRule,
+    /// Seventy-five moves by each side without a capture or pawn move.
+    SeventyFiveMoveRule,
+    /// Claimed once the same position occurred three times.
+    ThreefoldRepetition,
+    /// The same position occurred five times.
+    FivefoldRepetition,
+    /// A player ran out of time but the opponent could not have mated.
+    TimeoutWithInsufficientMaterial,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Checkmate { winner: Color },
+    Draw(DrawReason),
+}
+
+/// State that cannot be recovered from the board alone when unmaking a move.
+#[derive(Clone, Copy, Debug)]
+struct StateInfo {
+    mv: Move,
+    captured: Option<(Square, Piece)>,
+    castling_rights: CastlingRights,
+    en_passant: Option<Square>,
+    halfmove_clock: u32,
+    /// Hash of the position before the move.
+    hash: u64,
+}
+
+/// A chess position along with the moves that led to it.
+#[derive(Clone, Debug)]
+pub struct Position {
+    board: [Option<Piece>; 64],
+    by_color: [Bitboard; 2],
+    by_type: [Bitboard; 6],
+    side_to_move: Color,
+    castling_rights: CastlingRights,
+    en_passant: Option<Square>,
+    halfmove_clock: u32,
+    fullmove_number: u32,
+    history: Vec<StateInfo>,
+    /// Zobrist hash of the pieces on the board, kept up to date by
+    /// `set_piece`.
+    board_hash: u64,
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Position::from_fen(STARTING_FEN).expect("the starting FEN is valid")
+    }
+}
+
+impl Position {
+    /// The standard starting position.
+    pub fn new() -> Position {
+        Position::default()
+    }
+
+    /// A board without pieces, with white to move and no castling rights.
+    pub fn empty() -> Position {
+        Position {
+            board: [None; 64],
+            by_color: [Bitboard::EMPTY; 2],
+            by_type: [Bitboard::EMPTY; 6],
+            side_to_move: Color::White,
+            castling_rights: CastlingRights::NONE,
+            en_passant: None,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            history: vec![],
+            board_hash: 0,
+        }
+    }
+
+    pub fn piece_at(&self, square: Square) -> Option<Piece> {
+        self.board[square.index()]
+    }
+
+    pub fn set_piece(&mut self, square: Square, piece: Option<Piece>) {
+        let bit = Bitboard::from_square(square);
+
+        if let Some(old) = self.board[square.index()] {
+            self.by_color[old.color.index()] &= !bit;
+            self.by_type[old.piece_type.index()] &= !bit;
+            self.board_hash ^= zobrist::piece_key(old, square);
+        }
+        if let Some(new) = piece {
+            self.by_color[new.color.index()] |= bit;
+            self.by_type[new.piece_type.index()] |= bit;
+            self.board_hash ^= zobrist::piece_key(new, square);
+        }
+
+        self.board[square.index()] = piece;
+    }
+
+    pub fn pieces(&self) -> impl Iterator<Item = (Square, Piece)> + '_ {
+        self.occupied()
+            .filter_map(|square| self.piece_at(square).map(|piece| (square, piece)))
+    }
+
+    /// Squares holding any piece.
+    pub fn occupied(&self) -> Bitboard {
+        self.by_color[0] | self.by_color[1]
+    }
+
+    /// Squares holding a piece of the given color.
+    pub fn occupied_by(&self, color: Color) -> Bitboard {
+        self.by_color[color.index()]
+    }
+
+    /// Squares holding the given piece.
+    pub fn bitboard(&self, piece: Piece) -> Bitboard {
+        self.by_color[piece.color.index()] & self.by_type[piece.piece_type.index()]
+    }
+
+    pub fn side_to_move(&self) -> Color {
+        self.side_to_move
+    }
+
+    pub fn set_side_to_move(&mut self, color: Color) {
+        self.side_to_move = color;
+    }
+
+    pub fn castling_rights(&self) -> CastlingRights {
+        self.castling_rights
+    }
+
+    pub fn set_castling_rights(&mut self, castling_rights: CastlingRights) {
+        self.castling_rights = castling_rights;
+    }
+
+    /// Square skipped over by the last double pawn push.
+    pub fn en_passant(&self) -> Option<Square> {
+        self.en_passant
+    }
+
+    pub fn set_en_passant(&mut self, en_passant: Option<Square>) {
+        self.en_passant = en_passant;
+    }
+
+    /// Half moves since the last capture or pawn move.
+    pub fn halfmove_clock(&self) -> u32 {
+        self.halfmove_clock
+    }
+
+    pub fn set_halfmove_clock(&mut self, halfmove_clock: u32) {
+        self.halfmove_clock = halfmove_clock;
+    }
+
+    pub fn fullmove_number(&self) -> u32 {
+        self.fullmove_number
+    }
+
+    pub fn set_fullmove_number(&mut self, fullmove_number: u32) {
+        self.fullmove_number = fullmove_number;
+    }
+
+    /// Moves played so far, oldest first.
+    pub fn moves(&self) -> impl DoubleEndedIterator<Item = Move> + '_ {
+        self.history.iter().map(|state| state.mv)
+    }
+
+    pub fn last_move(&self) -> Option<Move> {
+        self.history.last().map(|state| state.mv)
+    }
+
+    /// Zobrist hash of the position, equal for positions that count as the
+    /// same for repetitions: same pieces, side to move, castling rights and
+    /// en passant capture.
+    pub fn hash(&self) -> u64 {
+        // The en passant square only matters if a pawn can capture on it
+        let en_passant = self.en_passant.filter(|&square| {
+            let pawns = self.bitboard(Piece::new(PieceType::Pawn, self.side_to_move));
+            !(pawn_attacks(self.side_to_move.opposite(), square) & pawns).is_empty()
+        });
+
+        self.board_hash
+            ^ zobrist::side_key(self.side_to_move)
+            ^ zobrist::castling_key(self.castling_rights)
+            ^ en_passant.map_or(0, zobrist::en_passant_key)
+    }
+
+    /// How many times the position occurred in the game, counting this one.
+    pub fn repetitions(&self) -> usize {
+        let hash = self.hash();
+
+        // Positions before the last capture or pawn move cannot come back,
+        // and every other one has the other side to move
+        let earlier = self
+            .history
+            .iter()
+            .rev()
+            .take(self.halfmove_clock as usize)
+            .skip(1)
+            .step_by(2)
+            .filter(|state| state.hash == hash)
+            .count();
+
+        earlier + 1
+    }
+
+    pub fn king_square(&self, color: Color) -> Option<Square> {
+        self.bitboard(Piece::new(PieceType::King, color)).first()
+    }
+
+    pub fn is_square_attacked(&self, square: Square, by_color: Color) -> bool {
+        is_square_attacked(self, square, by_color)
+    }
+
+    /// Whether the side to move is in check.
+    pub fn is_check(&self) -> bool {
+        self.king_square(self.side_to_move)
+            .is_some_and(|king| self.is_square_attacked(king, self.side_to_move.opposite()))
+    }
+
+    /// Moves of the side to move, ignoring whether they leave its king in check.
+    pub fn pseudo_legal_moves(&self) -> Vec<Move> {
+        let mut moves = Vec::with_capacity(64);
+        for square in self.occupied_by(self.side_to_move) {
+            if let Some(piece) = self.piece_at(square) {
+                get_valid_moves(self, square, piece, &mut moves);
+            }
+        }
+        moves
+    }
+
+    /// Moves of the side to move that do not leave its own king in check.
+    pub fn legal_moves(&self) -> Vec<Move> {
+        let mut scratch = self.without_history();
+        let color = self.side_to_move;
+
+        self.pseudo_legal_moves()
+            .into_iter()
+            .filter(|mv| {
+                scratch.make_move(*mv);
+                let is_legal = scratch
+                    .king_square(color)
+                    .is_none_or(|king| !scratch.is_square_attacked(king, color.opposite()));
+                scratch.unmake_move();
+                is_legal
+            })
+            .collect()
+    }
+
+    pub fn legal_moves_from(&self, from: Square) -> Vec<Move> {
+        self.legal_moves()
+            .into_iter()
+            .filter(|mv| mv.from == from)
+            .collect()
+    }
+
+    pub fn is_legal(&self, mv: Move) -> bool {
+        self.legal_moves().contains(&mv)
+    }
+
+    /// Whether `color` has the material to mate by any series of legal
+    /// moves: anything beyond a lone king, except a single minor piece
+    /// against a lone king.
+    pub fn can_checkmate(&self, color: Color) -> bool {
+        let has = |piece_type| !self.bitboard(Piece::new(piece_type, color)).is_empty();
+        if has(PieceType::Pawn) || has(PieceType::Rook) || has(PieceType::Queen) {
+            return true;
+        }
+
+        let minor_pieces = self.bitboard(Piece::new(PieceType::Knight, color)).count()
+            + self.bitboard(Piece::new(PieceType::Bishop, color)).count();
+        let opponent_has_pieces = self.occupied_by(color.opposite()).count() > 1;
+
+        // A lone minor piece can only mate with the help of the opponent's
+        // own pieces blocking their king in
+        minor_pieces >= 2 || (minor_pieces == 1 && opponent_has_pieces)
+    }
+
+    /// Whether neither side can mate by any series of legal moves, including
+    /// when only bishops on squares of the same color are left.
+    pub fn is_insufficient_material(&self) -> bool {
+        let kings = self.by_type[PieceType::King.index()];
+        let bishops = self.by_type[PieceType::Bishop.index()];
+        let others = self.occupied() & !kings & !bishops;
+
+        let light_squares = Bitboard(0x55aa_55aa_55aa_55aa);
+        let same_color_bishops =
+            (bishops & light_squares).is_empty() || (bishops & !light_squares).is_empty();
+
+        (!self.can_checkmate(Color::White) && !self.can_checkmate(Color::Black))
+            || (others.is_empty() && same_color_bishops)
+    }
+
+    /// The result of the game if it ended, by mate or by one of the draws
+    /// that apply without being claimed.
+    pub fn outcome(&self) -> Option<Outcome> {
+        if self.legal_moves().is_empty() {
+            return match self.is_check() {
+                true => Some(Outcome::Checkmate {
+                    winner: self.side_to_move.opposite(),
+                }),
+                false => Some(Outcome::Draw(DrawReason::Stalemate)),
+            };
+        }
+
+        let reason = if self.is_insufficient_material() {
+            DrawReason::InsufficientMaterial
+        } else if self.halfmove_clock >= 150 {
+            DrawReason::SeventyFiveMoveRule
+        } else if self.repetitions() >= 5 {
+            DrawReason::FivefoldRepetition
+        } else {
+            return None;
+        };
+
+        Some(Outcome::Draw(reason))
+    }
+
+    /// A draw either player may claim in this position.
+    pub fn claimable_draw(&self) -> Option<DrawReason> {
+        if self.repetitions() >= 3 {
+            Some(DrawReason::ThreefoldRepetition)
+        } else if self.halfmove_clock >= 100 {
+            Some(DrawReason::FiftyMoveRule)
+        } else {
+            None
+        }
+    }
+
+    /// Plays a move, which must be legal in this position.
+    pub fn make_move(&mut self, mv: Move) {
+        let piece = self.piece_at(mv.from).expect("no piece to move");
+        let color = piece.color;
+
+        let captured_square =
+            match piece.piece_type == PieceType::Pawn && Some(mv.to) == self.en_passant {
+                true => Square::new(mv.to.file(), mv.from.rank()),
+                false => mv.to,
+            };
+        let captured = self
+            .piece_at(captured_square)
+            .map(|captured| (captured_square, captured));
+
+        self.history.push(StateInfo {
+            mv,
+            captured,
+            castling_rights: self.castling_rights,
+            en_passant: self.en_passant,
+            halfmove_clock: self.halfmove_clock,
+            hash: self.hash(),
+        });
+
+        let piece_type = mv.promotion.unwrap_or(piece.piece_type);
+        self.set_piece(captured_square, None);
+        self.set_piece(mv.from, None);
+        self.set_piece(mv.to, Some(Piece::new(piece_type, color)));
+
+        // Castling also moves the rook to the other side of the king
+        if let Some(side) = castling_side(piece, mv) {
+            let rank = color.back_rank();
+            let rook = self.piece_at(Square::new(side.rook_file(), rank));
+            self.set_piece(Square::new(side.rook_file(), rank), None);
+            self.set_piece(Square::new(side.rook_target_file(), rank), rook);
+        }
+
+        // Moving the king or a rook, or capturing a rook, loses castling rights
+        for side in [CastlingSide::KingSide, CastlingSide::QueenSide] {
+            for color in [Color::White, Color::Black] {
+                let king_square = Square::new(4, color.back_rank());
+                let rook_square = Square::new(side.rook_file(), color.back_rank());
+
+                if [king_square, rook_square].contains(&mv.from) || mv.to == rook_square {
+                    self.castling_rights.remove(color, side);
+                }
+            }
+        }
+
+        self.en_passant = match piece.piece_type == PieceType::Pawn
+            && mv.from.rank().abs_diff(mv.to.rank()) == 2
+        {
+            true => Some(Square::new(
+                mv.from.file(),
+                (mv.from.rank() + mv.to.rank()) / 2,
+            )),
+            false => None,
+        };
+
+        self.halfmove_clock = match piece.piece_type == PieceType::Pawn || captured.is_some() {
+            true => 0,
+            false => self.halfmove_clock + 1,
+        };
+
+        if color == Color::Black {
+            self.fullmove_number += 1;
+        }
+        self.side_to_move.switch();
+    }
+
+    /// Takes back the last move played, returning it.
+    pub fn unmake_move(&mut self) -> Option<Move> {
+        let state = self.history.pop()?;
+        let mv = state.mv;
+
+        self.side_to_move.switch();
+        let color = self.side_to_move;
+        if color == Color::Black {
+            self.fullmove_number -= 1;
+        }
+
+        let moved = self.piece_at(mv.to).expect("no piece to take back");
+        let piece = match mv.promotion {
+            Some(_) => Piece::new(PieceType::Pawn, color),
+            None => moved,
+        };
+        self.set_piece(mv.to, None);
+        self.set_piece(mv.from, Some(piece));
+
+        if let Some((square, captured)) = state.captured {
+            self.set_piece(square, Some(captured));
+        }
+
+        if let Some(side) = castling_side(piece, mv) {
+            let rank = color.back_rank();
+            let rook = self.piece_at(Square::new(side.rook_target_file(), rank));
+            self.set_piece(Square::new(side.rook_target_file(), rank), None);
+            self.set_piece(Square::new(side.rook_file(), rank), rook);
+        }
+
+        self.castling_rights = state.castling_rights;
+        self.en_passant = state.en_passant;
+        self.halfmove_clock = state.halfmove_clock;
+
+        Some(mv)
+    }
+
+    /// The position before any of the recorded moves were played.
+    pub fn start_position(&self) -> Position {
+        let mut position = self.clone();
+        while position.unmake_move().is_some() {}
+        position
+    }
+
+    pub(crate) fn without_history(&self) -> Position {
+        Position {
+            history: vec![],
+            ..*self
+        }
+    }
+}
+
+pub(crate) fn castling_side(piece: Piece, mv: Move) -> Option<CastlingSide> {
+    if piece.piece_type != PieceType::King || mv.from.file().abs_diff(mv.to.file()) != 2 {
+        return None;
+    }
+
+    match mv.to.file() > mv.from.file() {
+        true => Some(CastlingSide::KingSide),
+        false => Some(CastlingSide::QueenSide),
+    }
+}
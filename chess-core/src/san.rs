@@ -0,0 +1,201 @@
+use std::fmt;
+
+use crate::{position::castling_side, CastlingSide, Move, PieceType, Position, Square};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SanError {
+    Invalid(String),
+    Illegal(String),
+    Ambiguous(String),
+}
+
+impl fmt::Display for SanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SanError::Invalid(san) => write!(f, "invalid move `{san}`"),
+            SanError::Illegal(san) => write!(f, "illegal move `{san}`"),
+            SanError::Ambiguous(san) => write!(f, "ambiguous move `{san}`"),
+        }
+    }
+}
+
+impl std::error::Error for SanError {}
+
+impl Position {
+    /// Formats a legal move in Standard Algebraic Notation, e.g. `Nbd7`,
+    /// `exd6`, `e8=Q+` or `O-O#`.
+    pub fn to_san(&self, mv: Move) -> String {
+        let piece = self.piece_at(mv.from).expect("no piece to move");
+
+        let mut san = match castling_side(piece, mv) {
+            Some(CastlingSide::KingSide) => "O-O".to_string(),
+            Some(CastlingSide::QueenSide) => "O-O-O".to_string(),
+            None => {
+                let is_capture = self.piece_at(mv.to).is_some()
+                    || (piece.piece_type == PieceType::Pawn && Some(mv.to) == self.en_passant());
+
+                let mut san = String::new();
+                match piece.piece_type {
+                    PieceType::Pawn if is_capture => san.push(file_char(mv.from.file())),
+                    PieceType::Pawn => {}
+                    piece_type => {
+                        san.push(piece_type.to_char());
+                        san.push_str(&self.disambiguation(mv));
+                    }
+                }
+
+                if is_capture {
+                    san.push('x');
+                }
+                san.push_str(&mv.to.to_string());
+
+                if let Some(promotion) = mv.promotion {
+                    san.push('=');
+                    san.push(promotion.to_char());
+                }
+                san
+            }
+        };
+
+        let mut position = self.without_history();
+        position.make_move(mv);
+        if position.is_check() {
+            san.push(match position.legal_moves().is_empty() {
+                true => '#',
+                false => '+',
+            });
+        }
+
+        san
+    }
+
+    /// Parses a move in Standard Algebraic Notation and finds the legal move
+    /// it describes. Check and annotation suffixes such as `+`, `#` or `!?`
+    /// are ignored.
+    pub fn parse_san(&self, san: &str) -> Result<Move, SanError> {
+        let invalid = || SanError::Invalid(san.to_string());
+
+        let text = san.trim_end_matches(['+', '#', '!', '?']);
+        let legal_moves = self.legal_moves();
+
+        let castling = match text {
+            "O-O" | "0-0" => Some(CastlingSide::KingSide),
+            "O-O-O" | "0-0-0" => Some(CastlingSide::QueenSide),
+            _ => None,
+        };
+        if let Some(side) = castling {
+            return legal_moves
+                .into_iter()
+                .find(|mv| {
+                    self.piece_at(mv.from)
+                        .is_some_and(|piece| castling_side(piece, *mv) == Some(side))
+                })
+                .ok_or_else(|| SanError::Illegal(san.to_string()));
+        }
+
+        // Promotion piece, written as `e8=Q` or `e8Q`
+        let (text, promotion) = match text.char_indices().last() {
+            Some((i, c)) if c.is_ascii_uppercase() => {
+                let promotion = PieceType::from_char(c).ok_or_else(invalid)?;
+                (text[..i].trim_end_matches('='), Some(promotion))
+            }
+            _ => (text, None),
+        };
+
+        let (piece_type, text) = match text.chars().next() {
+            Some(c) if c.is_ascii_uppercase() => {
+                (PieceType::from_char(c).ok_or_else(invalid)?, &text[1..])
+            }
+            _ => (PieceType::Pawn, text),
+        };
+
+        if text.len() < 2 || !text.is_char_boundary(text.len() - 2) {
+            return Err(invalid());
+        }
+        let (origin, target) = text.split_at(text.len() - 2);
+        let target: Square = target.parse().map_err(|_| invalid())?;
+
+        // Whatever is left before the target square is an optional origin
+        // file and/or rank followed by an optional capture mark
+        let mut from_file = None;
+        let mut from_rank = None;
+        for c in origin.trim_end_matches('x').chars() {
+            match c {
+                'a'..='h' if from_file.is_none() && from_rank.is_none() => {
+                    from_file = Some(c as u8 - b'a')
+                }
+                '1'..='8' if from_rank.is_none() => from_rank = Some(c as u8 - b'1'),
+                _ => return Err(invalid()),
+            }
+        }
+
+        let mut candidates = legal_moves.into_iter().filter(|mv| {
+            mv.to == target
+                && mv.promotion == promotion
+                && self
+                    .piece_at(mv.from)
+                    .is_some_and(|piece| piece.piece_type == piece_type)
+                && from_file.is_none_or(|file| mv.from.file() == file)
+                && from_rank.is_none_or(|rank| mv.from.rank() == rank)
+                // Pawn captures always name the file the pawn comes from
+                && (piece_type != PieceType::Pawn
+                    || mv.from.file() == mv.to.file()
+                    || from_file.is_some())
+        });
+
+        match (candidates.next(), candidates.next()) {
+            (Some(mv), None) => Ok(mv),
+            (Some(_), Some(_)) => Err(SanError::Ambiguous(san.to_string())),
+            (None, _) => Err(SanError::Illegal(san.to_string())),
+        }
+    }
+
+    /// The moves played so far in Standard Algebraic Notation.
+    pub fn san_moves(&self) -> Vec<String> {
+        let mut position = self.start_position();
+
+        self.moves()
+            .map(|mv| {
+                let san = position.to_san(mv);
+                position.make_move(mv);
+                san
+            })
+            .collect()
+    }
+
+    /// Origin file and/or rank needed to tell the move apart from moves of
+    /// other pieces of the same type to the same square.
+    fn disambiguation(&self, mv: Move) -> String {
+        let piece_type = self.piece_at(mv.from).map(|piece| piece.piece_type);
+        let others: Vec<Move> = self
+            .legal_moves()
+            .into_iter()
+            .filter(|other| {
+                other.to == mv.to
+                    && other.from != mv.from
+                    && self.piece_at(other.from).map(|piece| piece.piece_type) == piece_type
+            })
+            .collect();
+
+        if others.is_empty() {
+            return String::new();
+        }
+
+        let shares_file = others
+            .iter()
+            .any(|other| other.from.file() == mv.from.file());
+        let shares_rank = others
+            .iter()
+            .any(|other| other.from.rank() == mv.from.rank());
+
+        match (shares_file, shares_rank) {
+            (false, _) => file_char(mv.from.file()).to_string(),
+            (true, false) => (mv.from.rank() + 1).to_string(),
+            (true, true) => mv.from.to_string(),
+        }
+    }
+}
+
+fn file_char(file: u8) -> char {
+    (b'a' + file) as char
+}
@@ -0,0 +1,293 @@
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::{eval::piece_value, Move, PieceType, Position};
+
+/// Score of being checkmated on the current move. Mates further away score
+/// closer to zero so the quickest one is preferred.
+pub const MATE_SCORE: i32 = 30_000;
+
+const MAX_DEPTH: u32 = 64;
+
+/// How many nodes are searched between checks of the clock and stop flag.
+const CHECK_INTERVAL: u64 = 1024;
+
+/// When to stop searching. Without any limit, the search only ends when it
+/// is told to stop.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SearchLimits {
+    pub depth: Option<u32>,
+    pub time: Option<Duration>,
+}
+
+impl SearchLimits {
+    pub fn depth(depth: u32) -> SearchLimits {
+        SearchLimits {
+            depth: Some(depth),
+            time: None,
+        }
+    }
+
+    pub fn time(time: Duration) -> SearchLimits {
+        SearchLimits {
+            depth: None,
+            time: Some(time),
+        }
+    }
+}
+
+/// Best move found by the deepest completed iteration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchResult {
+    pub best_move: Option<Move>,
+    /// Centipawns from the point of view of the side to move.
+    pub score: i32,
+    pub depth: u32,
+    pub nodes: u64,
+    pub elapsed: Duration,
+}
+
+/// Searches the position with alpha-beta and iterative deepening until a
+/// limit is reached or `stop` is set. `report` is called after each
+/// completed iteration.
+pub fn search(
+    position: &Position,
+    limits: SearchLimits,
+    stop: &AtomicBool,
+    mut report: impl FnMut(&SearchResult),
+) -> SearchResult {
+    let mut searcher = Searcher {
+        position: position.clone(),
+        limits,
+        stop,
+        start: Instant::now(),
+        nodes: 0,
+        aborted: false,
+    };
+
+    let mut result = SearchResult {
+        best_move: None,
+        score: 0,
+        depth: 0,
+        nodes: 0,
+        elapsed: Duration::ZERO,
+    };
+
+    for depth in 1..=limits.depth.unwrap_or(MAX_DEPTH).min(MAX_DEPTH) {
+        let (best_move, score) = searcher.search_root(depth, result.best_move);
+
+        // A partial iteration can only be trusted if nothing was found yet
+        if searcher.aborted && result.best_move.is_some() {
+            break;
+        }
+
+        result = SearchResult {
+            best_move,
+            score,
+            depth,
+            nodes: searcher.nodes,
+            elapsed: searcher.start.elapsed(),
+        };
+        report(&result);
+
+        if searcher.aborted || best_move.is_none() || score.abs() >= MATE_SCORE - depth as i32 {
+            break;
+        }
+    }
+
+    result.nodes = searcher.nodes;
+    result.elapsed = searcher.start.elapsed();
+    result
+}
+
+struct Searcher<'a> {
+    position: Position,
+    limits: SearchLimits,
+    stop: &'a AtomicBool,
+    start: Instant,
+    nodes: u64,
+    aborted: bool,
+}
+
+impl Searcher<'_> {
+    fn search_root(&mut self, depth: u32, previous_best: Option<Move>) -> (Option<Move>, i32) {
+        let mut moves = self.position.legal_moves();
+        self.order_moves(&mut moves, previous_best);
+
+        if moves.is_empty() {
+            return (None, self.terminal_score(0));
+        }
+
+        let mut alpha = -MATE_SCORE - 1;
+        let beta = MATE_SCORE + 1;
+        let mut best_move = moves[0];
+
+        for mv in moves {
+            self.position.make_move(mv);
+            let score = -self.alpha_beta(depth - 1, 1, -beta, -alpha);
+            self.position.unmake_move();
+
+            if self.aborted {
+                break;
+            }
+            if score > alpha {
+                alpha = score;
+                best_move = mv;
+            }
+        }
+
+        (Some(best_move), alpha)
+    }
+
+    fn alpha_beta(&mut self, depth: u32, ply: u32, mut alpha: i32, beta: i32) -> i32 {
+        if depth == 0 {
+            return self.quiescence(alpha, beta);
+        }
+        if self.should_stop() {
+            return 0;
+        }
+
+        let mut moves = self.position.pseudo_legal_moves();
+        self.order_moves(&mut moves, None);
+
+        let mut has_legal_move = false;
+        for mv in moves {
+            if !self.make_legal_move(mv) {
+                continue;
+            }
+            has_legal_move = true;
+
+            let score = -self.alpha_beta(depth - 1, ply + 1, -beta, -alpha);
+            self.position.unmake_move();
+
+            if self.aborted {
+                return 0;
+            }
+            if score >= beta {
+                return beta;
+            }
+            alpha = alpha.max(score);
+        }
+
+        match has_legal_move {
+            true => alpha,
+            false => self.terminal_score(ply),
+        }
+    }
+
+    /// Only searches captures and promotions so the evaluation is not taken
+    /// in the middle of an exchange.
+    fn quiescence(&mut self, mut alpha: i32, beta: i32) -> i32 {
+        if self.should_stop() {
+            return 0;
+        }
+
+        let stand_pat = self.position.evaluate();
+        if stand_pat >= beta {
+            return beta;
+        }
+        alpha = alpha.max(stand_pat);
+
+        let mut moves = self.position.pseudo_legal_moves();
+        moves.retain(|mv| self.is_capture(*mv) || mv.promotion.is_some());
+        self.order_moves(&mut moves, None);
+
+        for mv in moves {
+            if !self.make_legal_move(mv) {
+                continue;
+            }
+
+            let score = -self.quiescence(-beta, -alpha);
+            self.position.unmake_move();
+
+            if self.aborted {
+                return 0;
+            }
+            if score >= beta {
+                return beta;
+            }
+            alpha = alpha.max(score);
+        }
+
+        alpha
+    }
+
+    /// Plays a pseudo-legal move, taking it back if it leaves the king in
+    /// check.
+    fn make_legal_move(&mut self, mv: Move) -> bool {
+        let color = self.position.side_to_move();
+        self.position.make_move(mv);
+
+        let is_legal = self
+            .position
+            .king_square(color)
+            .is_none_or(|king| !self.position.is_square_attacked(king, color.opposite()));
+        if !is_legal {
+            self.position.unmake_move();
+        }
+        is_legal
+    }
+
+    /// Score when the side to move has no legal moves.
+    fn terminal_score(&self, ply: u32) -> i32 {
+        match self.position.is_check() {
+            true => -MATE_SCORE + ply as i32,
+            false => 0,
+        }
+    }
+
+    fn is_capture(&self, mv: Move) -> bool {
+        self.position.piece_at(mv.to).is_some()
+            || (Some(mv.to) == self.position.en_passant()
+                && self
+                    .position
+                    .piece_at(mv.from)
+                    .is_some_and(|piece| piece.piece_type == PieceType::Pawn))
+    }
+
+    /// Tries the previous best move first, then captures of the most valuable
+    /// victim by the least valuable attacker, then promotions.
+    fn order_moves(&self, moves: &mut [Move], best_move: Option<Move>) {
+        moves.sort_by_cached_key(|mv| {
+            if Some(*mv) == best_move {
+                return i32::MIN;
+            }
+
+            let victim = match self.is_capture(*mv) {
+                true => self
+                    .position
+                    .piece_at(mv.to)
+                    .map_or(piece_value(PieceType::Pawn), |piece| {
+                        piece_value(piece.piece_type)
+                    }),
+                false => 0,
+            };
+            let attacker = self
+                .position
+                .piece_at(mv.from)
+                .map_or(0, |piece| piece_value(piece.piece_type));
+            let promotion = mv.promotion.map_or(0, piece_value);
+
+            match victim > 0 {
+                true => -(victim * 10 - attacker / 100) - promotion,
+                false => -promotion,
+            }
+        });
+    }
+
+    fn should_stop(&mut self) -> bool {
+        self.nodes += 1;
+
+        if self.nodes.is_multiple_of(CHECK_INTERVAL) {
+            let out_of_time = self
+                .limits
+                .time
+                .is_some_and(|time| self.start.elapsed() >= time);
+            self.aborted |= out_of_time || self.stop.load(Ordering::Relaxed);
+        }
+
+        self.aborted
+    }
+}
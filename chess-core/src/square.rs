@@ -0,0 +1,75 @@
+use std::{fmt, str::FromStr};
+
+/// A tile of the board, indexed from a1 (0) to h8 (63) rank by rank.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Square(u8);
+
+impl Square {
+    pub fn new(file: u8, rank: u8) -> Square {
+        assert!(file < 8 && rank < 8, "square out of the board");
+        Square(rank * 8 + file)
+    }
+
+    pub fn from_coords(file: i32, rank: i32) -> Option<Square> {
+        match (0..8).contains(&file) && (0..8).contains(&rank) {
+            true => Some(Square::new(file as u8, rank as u8)),
+            false => None,
+        }
+    }
+
+    pub fn from_index(index: usize) -> Square {
+        assert!(index < 64, "square out of the board");
+        Square(index as u8)
+    }
+
+    pub fn all() -> impl Iterator<Item = Square> {
+        (0..64).map(Square)
+    }
+
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    pub fn file(self) -> u8 {
+        self.0 % 8
+    }
+
+    pub fn rank(self) -> u8 {
+        self.0 / 8
+    }
+
+    pub fn offset(self, file_offset: i32, rank_offset: i32) -> Option<Square> {
+        Square::from_coords(
+            self.file() as i32 + file_offset,
+            self.rank() as i32 + rank_offset,
+        )
+    }
+}
+
+impl fmt::Display for Square {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", (b'a' + self.file()) as char, self.rank() + 1)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseSquareError(pub String);
+
+impl fmt::Display for ParseSquareError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid square `{}`", self.0)
+    }
+}
+
+impl std::error::Error for ParseSquareError {}
+
+impl FromStr for Square {
+    type Err = ParseSquareError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.as_bytes() {
+            [file @ b'a'..=b'h', rank @ b'1'..=b'8'] => Ok(Square::new(file - b'a', rank - b'1')),
+            _ => Err(ParseSquareError(s.to_string())),
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use std::{
+    fmt,
+    io::{self, BufRead, BufReader, Write},
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+    sync::{Arc, Mutex},
+};
+
+use super::{position_command, Info};
+use crate::{Move, Position, SearchLimits};
+
+#[derive(Debug)]
+pub enum UciError {
+    Io(io::Error),
+    EngineExited,
+    InvalidBestMove(String),
+}
+
+impl fmt::Display for UciError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UciError::Io(err) => write!(f, "cannot talk to the engine: {err}"),
+            UciError::EngineExited => write!(f, "the engine exited"),
+            UciError::InvalidBestMove(line) => write!(f, "invalid best move `{line}`"),
+        }
+    }
+}
+
+impl std::error::Error for UciError {}
+
+impl From<io::Error> for UciError {
+    fn from(err: io::Error) -> Self {
+        UciError::Io(err)
+    }
+}
+
+/// Move chosen at the end of a search. `best_move` is `None` when the engine
+/// has no legal move to play.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BestMove {
+    pub best_move: Option<Move>,
+    pub ponder: Option<Move>,
+}
+
+/// An external engine running as a subprocess, driven over UCI.
+pub struct UciEngine {
+    child: Child,
+    /// Shared with `StopHandle`s, so a search can be stopped while `go` is
+    /// waiting for its result.
+    stdin: Arc<Mutex<ChildStdin>>,
+    stdout: BufReader<ChildStdout>,
+    pub name: Option<String>,
+    pub author: Option<String>,
+}
+
+impl UciEngine {
+    /// Starts the engine and waits until it is ready to search.
+    pub fn spawn(mut command: Command) -> Result<UciEngine, UciError> {
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = BufReader::new(child.stdout.take().expect("stdout is piped"));
+
+        let mut engine = UciEngine {
+            child,
+            stdin: Arc::new(Mutex::new(stdin)),
+            stdout,
+            name: None,
+            author: None,
+        };
+
+        engine.send("uci")?;
+        loop {
+            let line = engine.read_line()?;
+            if let Some(name) = line.strip_prefix("id name ") {
+                engine.name = Some(name.to_string());
+            } else if let Some(author) = line.strip_prefix("id author ") {
+                engine.author = Some(author.to_string());
+            } else if line == "uciok" {
+                break;
+            }
+        }
+        engine.wait_ready()?;
+
+        Ok(engine)
+    }
+
+    pub fn set_option(&mut self, name: &str, value: &str) -> Result<(), UciError> {
+        self.send(&format!("setoption name {name} value {value}"))
+    }
+
+    /// Tells the engine the next position belongs to another game.
+    pub fn new_game(&mut self) -> Result<(), UciError> {
+        self.send("ucinewgame")?;
+        self.wait_ready()
+    }
+
+    /// Searches the position within the limits, passing each `info` line the
+    /// engine reports to `on_info`. Without limits the engine searches until
+    /// stopped through a `StopHandle`.
+    pub fn go(
+        &mut self,
+        position: &Position,
+        limits: SearchLimits,
+        mut on_info: impl FnMut(&Info),
+    ) -> Result<BestMove, UciError> {
+        self.send(&position_command(position))?;
+
+        let mut go = "go".to_string();
+        if let Some(depth) = limits.depth {
+            go.push_str(&format!(" depth {depth}"));
+        }
+        if let Some(time) = limits.time {
+            go.push_str(&format!(" movetime {}", time.as_millis()));
+        }
+        if limits.depth.is_none() && limits.time.is_none() {
+            go.push_str(" infinite");
+        }
+        self.send(&go)?;
+
+        loop {
+            let line = self.read_line()?;
+            if line.starts_with("info") {
+                on_info(&Info::parse(&line));
+            } else if line.starts_with("bestmove") {
+                return parse_best_move(&line);
+            }
+        }
+    }
+
+    /// Asks the engine to finish the current search as soon as possible.
+    pub fn stop(&self) -> Result<(), UciError> {
+        self.stop_handle().stop()
+    }
+
+    /// A handle to stop searches from another thread while `go` runs.
+    pub fn stop_handle(&self) -> StopHandle {
+        StopHandle(self.stdin.clone())
+    }
+
+    fn wait_ready(&mut self) -> Result<(), UciError> {
+        self.send("isready")?;
+        while self.read_line()? != "readyok" {}
+        Ok(())
+    }
+
+    fn send(&mut self, command: &str) -> Result<(), UciError> {
+        send(&self.stdin, command)
+    }
+
+    fn read_line(&mut self) -> Result<String, UciError> {
+        let mut line = String::new();
+        match self.stdout.read_line(&mut line)? {
+            0 => Err(UciError::EngineExited),
+            _ => Ok(line.trim().to_string()),
+        }
+    }
+}
+
+impl Drop for UciEngine {
+    fn drop(&mut self) {
+        if self.send("quit").is_err() {
+            let _ = self.child.kill();
+        }
+        let _ = self.child.wait();
+    }
+}
+
+/// Stops the searches of an engine, usable while it is borrowed by `go`.
+#[derive(Clone)]
+pub struct StopHandle(Arc<Mutex<ChildStdin>>);
+
+impl StopHandle {
+    /// Asks the engine to finish the current search as soon as possible.
+    pub fn stop(&self) -> Result<(), UciError> {
+        send(&self.0, "stop")
+    }
+}
+
+fn send(stdin: &Mutex<ChildStdin>, command: &str) -> Result<(), UciError> {
+    let mut stdin = stdin.lock().expect("engine stdin lock poisoned");
+    writeln!(stdin, "{command}")?;
+    stdin.flush()?;
+    Ok(())
+}
+
+fn parse_best_move(line: &str) -> Result<BestMove, UciError> {
+    let invalid = || UciError::InvalidBestMove(line.to_string());
+    let mut tokens = line.split_whitespace().skip(1);
+
+    let best_move = match tokens.next() {
+        Some("(none)" | "0000") => None,
+        Some(mv) => Some(mv.parse().map_err(|_| invalid())?),
+        None => return Err(invalid()),
+    };
+
+    let ponder = match (tokens.next(), tokens.next()) {
+        (Some("ponder"), Some(mv)) => mv.parse().ok(),
+        _ => None,
+    };
+
+    Ok(BestMove { best_move, ponder })
+}
@@ -0,0 +1,122 @@
+//! The Universal Chess Interface, the text protocol chess GUIs and engines
+//! talk over standard input and output.
+
+mod client;
+mod server;
+
+use std::fmt;
+
+pub use client::{BestMove, StopHandle, UciEngine, UciError};
+pub use server::UciServer;
+
+use crate::{Move, Position, MATE_SCORE, STARTING_FEN};
+
+/// Evaluation reported by an engine, from the point of view of the side to
+/// move.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Score {
+    Centipawns(i32),
+    /// Moves until mate, negative when the side to move gets mated.
+    Mate(i32),
+}
+
+impl Score {
+    /// Converts a score of `search`, telling mates apart from material.
+    pub fn from_search(score: i32) -> Score {
+        let plies_to_mate = MATE_SCORE - score.abs();
+        match plies_to_mate < 256 {
+            true => Score::Mate((plies_to_mate + 1) / 2 * score.signum()),
+            false => Score::Centipawns(score),
+        }
+    }
+}
+
+/// Search progress reported by an engine in an `info` line. Fields the
+/// engine left out are `None` or empty.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Info {
+    pub depth: Option<u32>,
+    pub score: Option<Score>,
+    pub nodes: Option<u64>,
+    pub time: Option<u64>,
+    pub pv: Vec<Move>,
+}
+
+impl Info {
+    /// Parses the fields following `info`, skipping those it does not know.
+    pub fn parse(line: &str) -> Info {
+        let mut info = Info::default();
+        let mut tokens = line.split_whitespace().skip_while(|token| *token == "info");
+
+        while let Some(token) = tokens.next() {
+            match token {
+                "depth" => info.depth = tokens.next().and_then(|depth| depth.parse().ok()),
+                "nodes" => info.nodes = tokens.next().and_then(|nodes| nodes.parse().ok()),
+                "time" => info.time = tokens.next().and_then(|time| time.parse().ok()),
+                "score" => {
+                    let kind = tokens.next();
+                    let value = tokens.next().and_then(|value| value.parse().ok());
+                    info.score = match (kind, value) {
+                        (Some("cp"), Some(value)) => Some(Score::Centipawns(value)),
+                        (Some("mate"), Some(value)) => Some(Score::Mate(value)),
+                        _ => None,
+                    };
+                }
+                // The principal variation runs to the end of the line
+                "pv" => info.pv = tokens.by_ref().map_while(|mv| mv.parse().ok()).collect(),
+                // A free form string also runs to the end of the line
+                "string" => break,
+                _ => {}
+            }
+        }
+
+        info
+    }
+}
+
+impl fmt::Display for Info {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "info")?;
+        if let Some(depth) = self.depth {
+            write!(f, " depth {depth}")?;
+        }
+        match self.score {
+            Some(Score::Centipawns(value)) => write!(f, " score cp {value}")?,
+            Some(Score::Mate(moves)) => write!(f, " score mate {moves}")?,
+            None => {}
+        }
+        if let Some(nodes) = self.nodes {
+            write!(f, " nodes {nodes}")?;
+        }
+        if let Some(time) = self.time {
+            write!(f, " time {time}")?;
+        }
+        if !self.pv.is_empty() {
+            write!(f, " pv")?;
+            for mv in &self.pv {
+                write!(f, " {mv}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The `position` command reaching the given position: its starting
+/// position followed by the moves played.
+pub fn position_command(position: &Position) -> String {
+    let start_fen = position.start_position().to_fen();
+    let mut command = match start_fen == STARTING_FEN {
+        true => "position startpos".to_string(),
+        false => format!("position fen {start_fen}"),
+    };
+
+    let mut moves = position.moves().peekable();
+    if moves.peek().is_some() {
+        command.push_str(" moves");
+        for mv in moves {
+            command.push_str(&format!(" {mv}"));
+        }
+    }
+
+    command
+}
@@ -0,0 +1,239 @@
+use std::{
+    io::{self, BufRead, Write},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use super::{Info, Score};
+use crate::{search, Color, Move, Position, SearchLimits};
+
+/// Moves to plan for when the GUI does not say how many are left until the
+/// next time control.
+const DEFAULT_MOVES_TO_GO: u64 = 30;
+
+/// Answers UCI commands with this crate's move generation and search,
+/// writing responses to `output`. Searches run on a background thread so
+/// `stop` and `isready` are handled while thinking.
+pub struct UciServer<W> {
+    position: Position,
+    output: Arc<Mutex<W>>,
+    search: Option<(JoinHandle<()>, Arc<AtomicBool>)>,
+}
+
+impl<W: Write + Send + 'static> UciServer<W> {
+    pub fn new(output: W) -> UciServer<W> {
+        UciServer {
+            position: Position::new(),
+            output: Arc::new(Mutex::new(output)),
+            search: None,
+        }
+    }
+
+    /// Handles commands until `quit` or the end of the input.
+    pub fn run(mut self, input: impl BufRead) -> io::Result<()> {
+        for line in input.lines() {
+            if !self.handle(&line?) {
+                break;
+            }
+        }
+        self.stop_search();
+        Ok(())
+    }
+
+    /// Handles a single command, returning `false` once told to quit.
+    pub fn handle(&mut self, line: &str) -> bool {
+        let mut tokens = line.split_whitespace();
+
+        match tokens.next() {
+            Some("uci") => {
+                self.send("id name bevy-chess");
+                self.send("id author the bevy-chess developers");
+                self.send("uciok");
+            }
+            Some("isready") => self.send("readyok"),
+            Some("ucinewgame") => {
+                self.stop_search();
+                self.position = Position::new();
+            }
+            Some("position") => {
+                self.stop_search();
+                match parse_position(tokens) {
+                    Ok(position) => self.position = position,
+                    Err(err) => self.send(&format!("info string {err}")),
+                }
+            }
+            Some("go") => self.go(tokens),
+            Some("stop") => self.stop_search(),
+            Some("quit") => return false,
+            // Unknown commands are ignored, as the protocol asks
+            _ => {}
+        }
+
+        true
+    }
+
+    fn go<'a>(&mut self, tokens: impl Iterator<Item = &'a str>) {
+        self.stop_search();
+
+        let go = GoOptions::parse(tokens);
+        if let Some(depth) = go.perft {
+            self.perft(depth);
+            return;
+        }
+
+        let position = self.position.clone();
+        let limits = go.limits(position.side_to_move() == Color::White);
+        let infinite = go.infinite;
+        let output = self.output.clone();
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+
+        let handle = thread::spawn(move || {
+            let result = search(&position, limits, &thread_stop, |result| {
+                let info = Info {
+                    depth: Some(result.depth),
+                    score: Some(Score::from_search(result.score)),
+                    nodes: Some(result.nodes),
+                    time: Some(result.elapsed.as_millis() as u64),
+                    pv: result.best_move.into_iter().collect(),
+                };
+                send(&output, &info.to_string());
+            });
+
+            // `go infinite` answers only once told to stop, even when the
+            // search ended early, e.g. on finding a mate
+            while infinite && !thread_stop.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(1));
+            }
+
+            let best_move = result
+                .best_move
+                .map_or("0000".to_string(), |mv| mv.to_string());
+            send(&output, &format!("bestmove {best_move}"));
+        });
+
+        self.search = Some((handle, stop));
+    }
+
+    fn perft(&mut self, depth: u32) {
+        let divide = self.position.perft_divide(depth);
+        for (mv, nodes) in &divide {
+            self.send(&format!("{mv}: {nodes}"));
+        }
+
+        let nodes: u64 = divide.iter().map(|(_, nodes)| nodes).sum();
+        self.send("");
+        self.send(&format!("Nodes searched: {nodes}"));
+    }
+
+    /// Stops the running search, if any, once it has sent its best move.
+    fn stop_search(&mut self) {
+        if let Some((handle, stop)) = self.search.take() {
+            stop.store(true, Ordering::Relaxed);
+            let _ = handle.join();
+        }
+    }
+
+    fn send(&self, line: &str) {
+        send(&self.output, line);
+    }
+}
+
+fn send<W: Write>(output: &Mutex<W>, line: &str) {
+    let mut output = output.lock().expect("output lock poisoned");
+    let _ = writeln!(output, "{line}");
+    let _ = output.flush();
+}
+
+/// Parses the arguments of `position`: `startpos` or `fen <FEN>`, optionally
+/// followed by `moves` and the moves played from there.
+fn parse_position<'a>(mut tokens: impl Iterator<Item = &'a str>) -> Result<Position, String> {
+    let mut position = match tokens.next() {
+        Some("startpos") => Position::new(),
+        Some("fen") => {
+            let fen: Vec<&str> = tokens
+                .by_ref()
+                .take_while(|token| *token != "moves")
+                .collect();
+            Position::from_fen(&fen.join(" ")).map_err(|err| err.to_string())?
+        }
+        _ => return Err("expected `startpos` or `fen`".to_string()),
+    };
+
+    for token in tokens.skip_while(|token| *token == "moves") {
+        let mv: Move = token.parse().map_err(|err| format!("{err}"))?;
+        if !position.is_legal(mv) {
+            return Err(format!("illegal move `{mv}`"));
+        }
+        position.make_move(mv);
+    }
+
+    Ok(position)
+}
+
+#[derive(Default)]
+struct GoOptions {
+    depth: Option<u32>,
+    move_time: Option<u64>,
+    white_time: Option<u64>,
+    black_time: Option<u64>,
+    white_increment: u64,
+    black_increment: u64,
+    moves_to_go: Option<u64>,
+    infinite: bool,
+    perft: Option<u32>,
+}
+
+impl GoOptions {
+    fn parse<'a>(mut tokens: impl Iterator<Item = &'a str>) -> GoOptions {
+        let mut go = GoOptions::default();
+
+        while let Some(token) = tokens.next() {
+            let mut value = || tokens.next().and_then(|value| value.parse::<u64>().ok());
+            match token {
+                "depth" => go.depth = value().map(|depth| depth as u32),
+                "movetime" => go.move_time = value(),
+                "wtime" => go.white_time = value(),
+                "btime" => go.black_time = value(),
+                "winc" => go.white_increment = value().unwrap_or(0),
+                "binc" => go.black_increment = value().unwrap_or(0),
+                "movestogo" => go.moves_to_go = value(),
+                "perft" => go.perft = value().map(|depth| depth as u32),
+                "infinite" => go.infinite = true,
+                _ => {}
+            }
+        }
+
+        go
+    }
+
+    /// Search limits, budgeting a share of the remaining clock time when no
+    /// fixed move time is given.
+    fn limits(&self, white_to_move: bool) -> SearchLimits {
+        let (clock, increment) = match white_to_move {
+            true => (self.white_time, self.white_increment),
+            false => (self.black_time, self.black_increment),
+        };
+
+        let time = match (self.infinite, self.move_time, clock) {
+            (true, _, _) => None,
+            (false, Some(move_time), _) => Some(move_time),
+            (false, None, Some(clock)) => {
+                let moves_to_go = self.moves_to_go.unwrap_or(DEFAULT_MOVES_TO_GO).max(1);
+                let budget = clock / moves_to_go + increment / 2;
+                // Keep a margin for the time it takes to answer
+                Some(budget.min(clock.saturating_sub(50)).max(1))
+            }
+            (false, None, None) => None,
+        };
+
+        SearchLimits {
+            depth: self.depth,
+            time: time.map(Duration::from_millis),
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! Random keys XORed together into a hash of a position, so positions can be
+//! compared for repetitions without comparing whole boards.
+
+use crate::{CastlingRights, Color, Piece, Square};
+
+const PIECE_KEYS: [[[u64; 64]; 6]; 2] = {
+    let mut keys = [[[0; 64]; 6]; 2];
+    let mut i = 0;
+    while i < 2 * 6 * 64 {
+        keys[i / 384][i / 64 % 6][i % 64] = key(i);
+        i += 1;
+    }
+    keys
+};
+
+const CASTLING_KEYS: [u64; 16] = {
+    let mut keys = [0; 16];
+    let mut i = 0;
+    while i < 16 {
+        keys[i] = key(768 + i);
+        i += 1;
+    }
+    keys
+};
+
+const EN_PASSANT_KEYS: [u64; 8] = {
+    let mut keys = [0; 8];
+    let mut i = 0;
+    while i < 8 {
+        keys[i] = key(784 + i);
+        i += 1;
+    }
+    keys
+};
+
+const BLACK_TO_MOVE_KEY: u64 = key(792);
+
+/// The `index`th output of a SplitMix64 generator, see
+/// https://prng.di.unimi.it/splitmix64.c
+const fn key(index: usize) -> u64 {
+    let mut z = 0x3243_f6a8_885a_308d_u64
+        .wrapping_add((index as u64 + 1).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+pub(crate) fn piece_key(piece: Piece, square: Square) -> u64 {
+    PIECE_KEYS[piece.color.index()][piece.piece_type.index()][square.index()]
+}
+
+pub(crate) fn castling_key(castling_rights: CastlingRights) -> u64 {
+    CASTLING_KEYS[castling_rights.index()]
+}
+
+pub(crate) fn en_passant_key(square: Square) -> u64 {
+    EN_PASSANT_KEYS[square.file() as usize]
+}
+
+pub(crate) fn side_key(color: Color) -> u64 {
+    match color {
+        Color::White => 0,
+        Color::Black => BLACK_TO_MOVE_KEY,
+    }
+}
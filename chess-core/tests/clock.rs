@@ -0,0 +1,118 @@
+use std::time::Duration;
+
+use chess_core::{ChessClock, Color, Position, TimeControl};
+
+fn secs(seconds: u64) -> Duration {
+    Duration::from_secs(seconds)
+}
+
+#[test]
+fn time_controls_parse() {
+    assert_eq!(
+        "5".parse(),
+        Ok(TimeControl::SuddenDeath { initial: secs(300) })
+    );
+    assert_eq!(
+        "3+2".parse(),
+        Ok(TimeControl::Fischer {
+            initial: secs(180),
+            increment: secs(2),
+        })
+    );
+    assert_eq!(
+        "0.5d3".parse(),
+        Ok(TimeControl::Bronstein {
+            initial: secs(30),
+            delay: secs(3),
+        })
+    );
+    assert!("0".parse::<TimeControl>().is_err());
+    assert!("5+".parse::<TimeControl>().is_err());
+    assert!("fast".parse::<TimeControl>().is_err());
+    assert!("-1".parse::<TimeControl>().is_err());
+    assert!("1e300".parse::<TimeControl>().is_err());
+    assert!("5+1e300".parse::<TimeControl>().is_err());
+    assert!("inf".parse::<TimeControl>().is_err());
+}
+
+#[test]
+fn only_the_side_to_move_ticks() {
+    let mut clock = ChessClock::new("1".parse().unwrap());
+    clock.tick(Color::White, secs(10));
+
+    assert_eq!(clock.remaining(Color::White), secs(50));
+    assert_eq!(clock.remaining(Color::Black), secs(60));
+}
+
+#[test]
+fn fischer_adds_increment_after_each_move() {
+    let mut clock = ChessClock::new("1+5".parse().unwrap());
+    clock.tick(Color::White, secs(2));
+    clock.press(Color::White);
+
+    assert_eq!(clock.remaining(Color::White), secs(63));
+}
+
+#[test]
+fn bronstein_gives_back_time_used_up_to_delay() {
+    let mut clock = ChessClock::new("1d5".parse().unwrap());
+
+    clock.tick(Color::White, secs(2));
+    clock.press(Color::White);
+    assert_eq!(clock.remaining(Color::White), secs(60));
+
+    clock.tick(Color::Black, secs(8));
+    clock.press(Color::Black);
+    assert_eq!(clock.remaining(Color::Black), secs(57));
+}
+
+#[test]
+fn flag_falls_at_zero_and_stays_down() {
+    let mut clock = ChessClock::new("1+5".parse().unwrap());
+    clock.tick(Color::Black, secs(61));
+    clock.press(Color::Black);
+
+    assert!(clock.is_flagged(Color::Black));
+    assert!(!clock.is_flagged(Color::White));
+}
+
+#[test]
+fn mating_material() {
+    let can_checkmate = |fen: &str, color| Position::from_fen(fen).unwrap().can_checkmate(color);
+
+    assert!(!can_checkmate(
+        "4k3/8/8/8/8/8/8/4K3 w - - 0 1",
+        Color::White
+    ));
+    assert!(!can_checkmate(
+        "4k3/8/8/8/8/8/8/3NK3 w - - 0 1",
+        Color::White
+    ));
+    assert!(can_checkmate(
+        "4k3/8/8/8/8/8/8/2BNK3 w - - 0 1",
+        Color::White
+    ));
+    assert!(can_checkmate(
+        "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1",
+        Color::White
+    ));
+    // The lone knight could mate a king hemmed in by its own pawn
+    assert!(can_checkmate(
+        "4k3/4p3/8/8/8/8/8/3NK3 w - - 0 1",
+        Color::White
+    ));
+    assert!(can_checkmate(
+        "4k3/4p3/8/8/8/8/8/3NK3 w - - 0 1",
+        Color::Black
+    ));
+}
+
+#[test]
+fn huge_increments_do_not_overflow() {
+    let time_control = "300000000000000000+10000000000000000000".parse().unwrap();
+    let mut clock = ChessClock::new(time_control);
+
+    clock.press(Color::White);
+    clock.press(Color::White);
+    assert_eq!(clock.remaining(Color::White), Duration::MAX);
+}
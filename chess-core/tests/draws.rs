@@ -0,0 +1,133 @@
+use chess_core::{DrawReason, Outcome, Position};
+
+fn play(position: &mut Position, moves: &str) {
+    for uci in moves.split_whitespace() {
+        let mv = uci.parse().unwrap();
+        assert!(position.is_legal(mv), "{uci} is illegal");
+        position.make_move(mv);
+    }
+}
+
+fn draw(reason: DrawReason) -> Option<Outcome> {
+    Some(Outcome::Draw(reason))
+}
+
+const KNIGHT_DANCE: &str = "g1f3 g8f6 f3g1 f6g8";
+
+#[test]
+fn transpositions_hash_the_same() {
+    let mut a = Position::new();
+    let mut b = Position::new();
+    play(&mut a, "g1f3 g8f6 b1c3");
+    play(&mut b, "b1c3 g8f6 g1f3");
+
+    assert_eq!(a.hash(), b.hash());
+    assert_ne!(a.hash(), Position::new().hash());
+    assert_eq!(
+        a.hash(),
+        Position::from_fen(&a.to_fen()).unwrap().hash(),
+        "hash does not depend on how the position was set up"
+    );
+}
+
+#[test]
+fn hash_ignores_en_passant_square_nobody_can_capture_on() {
+    let mut pushed = Position::new();
+    play(&mut pushed, "e2e4");
+    let without = Position::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1");
+    assert_eq!(pushed.hash(), without.unwrap().hash());
+
+    let mut capturable = Position::from_fen("4k3/3p4/8/4P3/8/8/8/4K3 b - - 0 1").unwrap();
+    play(&mut capturable, "d7d5");
+    let without = Position::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - - 0 2");
+    assert_ne!(capturable.hash(), without.unwrap().hash());
+}
+
+#[test]
+fn threefold_repetition_can_be_claimed() {
+    let mut position = Position::new();
+    play(&mut position, KNIGHT_DANCE);
+    assert_eq!(position.repetitions(), 2);
+    assert_eq!(position.claimable_draw(), None);
+
+    play(&mut position, KNIGHT_DANCE);
+    assert_eq!(position.repetitions(), 3);
+    assert_eq!(
+        position.claimable_draw(),
+        Some(DrawReason::ThreefoldRepetition)
+    );
+    assert_eq!(position.outcome(), None);
+}
+
+#[test]
+fn fivefold_repetition_ends_the_game() {
+    let mut position = Position::new();
+    for _ in 0..3 {
+        play(&mut position, KNIGHT_DANCE);
+    }
+    assert_eq!(position.outcome(), None);
+
+    play(&mut position, KNIGHT_DANCE);
+    assert_eq!(position.repetitions(), 5);
+    assert_eq!(position.outcome(), draw(DrawReason::FivefoldRepetition));
+}
+
+#[test]
+fn lost_castling_rights_make_a_different_position() {
+    let mut position = Position::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    play(&mut position, "e1d1 e8d8 d1e1 d8e8");
+
+    assert_eq!(position.repetitions(), 1);
+}
+
+#[test]
+fn fifty_and_seventy_five_move_rules() {
+    let mut position = Position::from_fen("4k3/8/8/8/8/8/4P3/R3K3 w - - 99 80").unwrap();
+    assert_eq!(position.claimable_draw(), None);
+    play(&mut position, "a1a2");
+    assert_eq!(position.claimable_draw(), Some(DrawReason::FiftyMoveRule));
+    assert_eq!(position.outcome(), None);
+
+    let mut position = Position::from_fen("4k3/8/8/8/8/8/4P3/R3K3 w - - 149 80").unwrap();
+    play(&mut position, "a1a2");
+    assert_eq!(position.outcome(), draw(DrawReason::SeventyFiveMoveRule));
+
+    // A pawn move resets the count
+    let mut position = Position::from_fen("4k3/8/8/8/8/8/4P3/R3K3 w - - 149 80").unwrap();
+    play(&mut position, "e2e3");
+    assert_eq!(position.outcome(), None);
+}
+
+#[test]
+fn mate_on_the_seventy_fifth_move_stands() {
+    let mut position = Position::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 149 80").unwrap();
+    play(&mut position, "a1a8");
+
+    assert!(matches!(
+        position.outcome(),
+        Some(Outcome::Checkmate { .. })
+    ));
+}
+
+#[test]
+fn insufficient_material() {
+    let dead = |fen| Position::from_fen(fen).unwrap().is_insufficient_material();
+
+    assert!(dead("4k3/8/8/8/8/8/8/4K3 w - - 0 1"));
+    assert!(dead("4k3/8/8/8/8/8/8/2B1K3 w - - 0 1"));
+    assert!(dead("4k3/8/8/8/8/8/8/3NK3 w - - 0 1"));
+    assert!(
+        dead("4kb2/8/8/8/8/8/8/2B1K3 w - - 0 1"),
+        "bishops on same colored squares"
+    );
+    assert!(
+        !dead("2b1k3/8/8/8/8/8/8/2B1K3 w - - 0 1"),
+        "bishops on opposite colored squares"
+    );
+    assert!(!dead("3nk3/8/8/8/8/8/8/3NK3 w - - 0 1"));
+    assert!(!dead("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1"));
+
+    let mut position = Position::from_fen("4k3/8/8/8/8/8/4q3/4K3 w - - 0 1").unwrap();
+    play(&mut position, "e1e2");
+    assert_eq!(position.outcome(), draw(DrawReason::InsufficientMaterial));
+}
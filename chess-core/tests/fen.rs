@@ -0,0 +1,164 @@
+use chess_core::{CastlingSide, Color, FenError, Position, Square, STARTING_FEN};
+
+fn square(name: &str) -> Square {
+    name.parse().unwrap()
+}
+
+#[test]
+fn round_trips() {
+    for fen in [
+        STARTING_FEN,
+        "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w Kq - 3 17",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 b - - 49 80",
+    ] {
+        assert_eq!(Position::from_fen(fen).unwrap().to_fen(), fen);
+    }
+}
+
+#[test]
+fn fields_are_read() {
+    let position =
+        Position::from_fen("rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b Qk e3 0 3").unwrap();
+
+    assert_eq!(position.side_to_move(), Color::Black);
+    assert!(position
+        .castling_rights()
+        .has(Color::White, CastlingSide::QueenSide));
+    assert!(!position
+        .castling_rights()
+        .has(Color::White, CastlingSide::KingSide));
+    assert!(position
+        .castling_rights()
+        .has(Color::Black, CastlingSide::KingSide));
+    assert_eq!(position.en_passant(), Some(square("e3")));
+    assert_eq!(position.fullmove_number(), 3);
+}
+
+#[test]
+fn move_counters_are_optional() {
+    let position = Position::from_fen("4k3/8/8/8/8/8/8/4K3 w -").err();
+    assert_eq!(position, Some(FenError::MissingField("en passant")));
+
+    let position = Position::from_fen("4k3/8/8/8/8/8/8/4K3 w - -").unwrap();
+    assert_eq!(position.to_fen(), "4k3/8/8/8/8/8/8/4K3 w - - 0 1");
+}
+
+#[test]
+fn castling_rights_without_king_or_rook_are_dropped() {
+    let position = Position::from_fen("4k3/8/8/8/8/8/8/4K2R w KQkq - 0 1").unwrap();
+    assert_eq!(position.to_fen(), "4k3/8/8/8/8/8/8/4K2R w K - 0 1");
+}
+
+#[test]
+fn exported_after_moves() {
+    let mut position = Position::new();
+    for uci in ["e2e4", "c7c5", "g1f3"] {
+        position.make_move(uci.parse().unwrap());
+    }
+
+    assert_eq!(
+        position.to_fen(),
+        "rnbqkbnr/pp1ppppp/8/2p5/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2"
+    );
+}
+
+#[test]
+fn errors() {
+    let error = |fen| Position::from_fen(fen).unwrap_err();
+
+    assert_eq!(error(""), FenError::MissingField("piece placement"));
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4K3"),
+        FenError::MissingField("side to move")
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4K3 w"),
+        FenError::MissingField("castling rights")
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/4K3 w - - 0 1"),
+        FenError::InvalidBoard("4k3/8/8/8/8/8/4K3".to_string())
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4K4 w - - 0 1"),
+        FenError::InvalidBoard("4k3/8/8/8/8/8/8/4K4".to_string())
+    );
+    let digits = "8".repeat(32);
+    assert_eq!(
+        Position::from_fen(&format!("4k3/8/8/8/8/8/8/{digits} w - - 0 1")).unwrap_err(),
+        FenError::InvalidBoard(format!("4k3/8/8/8/8/8/8/{digits}"))
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4X3 w - - 0 1"),
+        FenError::InvalidBoard("4k3/8/8/8/8/8/8/4X3".to_string())
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4K3 white - - 0 1"),
+        FenError::InvalidSideToMove("white".to_string())
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4K3 w KX - 0 1"),
+        FenError::InvalidCastlingRights("KX".to_string())
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4K3 w - e9 0 1"),
+        FenError::InvalidEnPassant("e9".to_string())
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4K3 w - 0 1 1"),
+        FenError::InvalidEnPassant("0".to_string())
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4K3 w - - x 1"),
+        FenError::InvalidMoveCounter("x".to_string())
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4K3 w - - 0 -1"),
+        FenError::InvalidMoveCounter("-1".to_string())
+    );
+    assert_eq!(error("8/8/8/8/8/8/8/4K3 w - - 0 1"), FenError::InvalidKings);
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/3KK3 w - - 0 1"),
+        FenError::InvalidKings
+    );
+}
+
+#[test]
+fn en_passant_square_must_be_behind_a_pushed_pawn() {
+    assert_eq!(
+        Position::from_fen("4k3/8/8/8/3P4/8/8/4K3 w - e5 0 1").unwrap_err(),
+        FenError::InvalidEnPassant("e5".to_string())
+    );
+    assert_eq!(
+        Position::from_fen("4k3/8/8/3pP3/8/8/8/4K3 b - d6 0 1").unwrap_err(),
+        FenError::InvalidEnPassant("d6".to_string()),
+        "rank 6 is for white to move"
+    );
+    assert_eq!(
+        Position::from_fen("4k3/8/8/3P4/8/8/8/4K3 w - e6 0 1").unwrap_err(),
+        FenError::InvalidEnPassant("e6".to_string()),
+        "no pawn to capture"
+    );
+    assert_eq!(
+        Position::from_fen("4k3/8/4p3/3Pp3/8/8/8/4K3 w - e6 0 1").unwrap_err(),
+        FenError::InvalidEnPassant("e6".to_string()),
+        "the square is occupied"
+    );
+    assert_eq!(
+        Position::from_fen("4k3/4p3/8/3Pp3/8/8/8/4K3 w - e6 0 1").unwrap_err(),
+        FenError::InvalidEnPassant("e6".to_string()),
+        "the pawn cannot have come from e7"
+    );
+    assert!(Position::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").is_ok());
+    assert!(Position::from_fen("4k3/8/8/8/3Pp3/8/8/4K3 b - d3 0 1").is_ok());
+}
+
+#[test]
+fn side_not_to_move_must_not_be_in_check() {
+    assert_eq!(
+        Position::from_fen("4k3/8/8/8/8/8/4Q3/4K3 w - - 0 1").unwrap_err(),
+        FenError::OpponentInCheck
+    );
+    assert!(Position::from_fen("4k3/8/8/8/8/8/4Q3/4K3 b - - 0 1").is_ok());
+}
@@ -0,0 +1,125 @@
+use std::{net::TcpListener, thread};
+
+use chess_core::{
+    net::{Message, NetError, Peer},
+    Color, Move, Position,
+};
+
+fn mv(uci: &str) -> Move {
+    uci.parse().unwrap()
+}
+
+/// Connects a host and a joining peer over localhost.
+fn connect(start_position: Position, host_color: Color) -> (Peer, Peer) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+
+    let host = thread::spawn(move || Peer::host(&listener, &start_position, host_color).unwrap());
+    let joined = Peer::join(address).unwrap();
+
+    (host.join().unwrap(), joined)
+}
+
+#[test]
+fn joining_player_gets_other_color_and_position() {
+    let position = Position::from_fen("4k3/8/8/8/8/8/4P3/4K3 b - - 0 1").unwrap();
+    let (host, joined) = connect(position.clone(), Color::Black);
+
+    assert_eq!(host.color, Color::Black);
+    assert_eq!(joined.color, Color::White);
+    assert_eq!(joined.start_position.to_fen(), position.to_fen());
+}
+
+/// Plays a move on the sender's game and sends it to the receiver, which
+/// applies it to its own.
+fn play(
+    sender: &mut Peer,
+    sender_game: &mut Position,
+    receiver: &mut Peer,
+    receiver_game: &mut Position,
+    uci: &str,
+) {
+    sender_game.make_move(mv(uci));
+    sender.send(&Message::Move(mv(uci))).unwrap();
+
+    let Message::Move(received) = receiver.receive().unwrap() else {
+        panic!("expected a move");
+    };
+    receiver.apply_remote(receiver_game, received).unwrap();
+}
+
+#[test]
+fn moves_travel_both_ways() {
+    let (mut host, mut joined) = connect(Position::new(), Color::White);
+    let mut host_game = Position::new();
+    let mut joined_game = Position::new();
+
+    play(
+        &mut host,
+        &mut host_game,
+        &mut joined,
+        &mut joined_game,
+        "e2e4",
+    );
+    play(
+        &mut joined,
+        &mut joined_game,
+        &mut host,
+        &mut host_game,
+        "e7e5",
+    );
+    play(
+        &mut host,
+        &mut host_game,
+        &mut joined,
+        &mut joined_game,
+        "g1f3",
+    );
+
+    assert_eq!(host_game.to_fen(), joined_game.to_fen());
+}
+
+#[test]
+fn remote_moves_are_validated() {
+    let (host, _joined) = connect(Position::new(), Color::White);
+    let mut game = Position::new();
+
+    // White is the host's own color, so the other player cannot move now
+    assert!(matches!(
+        host.apply_remote(&mut game, mv("e2e4")),
+        Err(NetError::OutOfTurn(_))
+    ));
+
+    game.make_move(mv("e2e4"));
+    assert!(matches!(
+        host.apply_remote(&mut game, mv("e7e4")),
+        Err(NetError::IllegalMove(_))
+    ));
+    assert!(host.apply_remote(&mut game, mv("e7e5")).is_ok());
+}
+
+#[test]
+fn disconnect_is_reported() {
+    let (host, mut joined) = connect(Position::new(), Color::White);
+    drop(host);
+
+    assert!(matches!(joined.receive(), Err(NetError::Disconnected)));
+}
+
+#[test]
+fn messages_round_trip() {
+    for message in [
+        Message::Hello {
+            version: 1,
+            color: Color::Black,
+            fen: "4k3/8/8/8/8/8/8/4K3 w - - 0 1".to_string(),
+        },
+        Message::Move(mv("a7a8q")),
+        Message::Bye,
+    ] {
+        assert_eq!(message.to_string().parse::<Message>().unwrap(), message);
+    }
+
+    assert!("move e9e4".parse::<Message>().is_err());
+    assert!("castle".parse::<Message>().is_err());
+}
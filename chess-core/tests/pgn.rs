@@ -0,0 +1,162 @@
+use chess_core::{Color, FenError, GameResult, Move, Pgn, PgnError, Position, SanError};
+
+fn mv(uci: &str) -> Move {
+    uci.parse().unwrap()
+}
+
+fn moves(ucis: &str) -> Vec<Move> {
+    ucis.split_whitespace().map(mv).collect()
+}
+
+#[test]
+fn reads_tags_moves_and_result() {
+    let pgn: Pgn = r#"[Event "Casual \"blitz\" game"]
+[White "Alice"]
+[Black "Bob"]
+[Result "1-0"]
+
+1. e4 e5 2. Bc4 Nc6 3. Qh5 Nf6 4. Qxf7# 1-0
+"#
+    .parse()
+    .unwrap();
+
+    assert_eq!(pgn.tag("Event"), Some("Casual \"blitz\" game"));
+    assert_eq!(pgn.tag("White"), Some("Alice"));
+    assert_eq!(pgn.tag("Round"), None);
+    assert_eq!(pgn.result, GameResult::WhiteWins);
+    assert_eq!(pgn.moves, moves("e2e4 e7e5 f1c4 b8c6 d1h5 g8f6 h5f7"));
+    assert!(pgn.end_position().legal_moves().is_empty());
+}
+
+#[test]
+fn skips_comments_variations_and_annotations() {
+    let pgn: Pgn = "1. e4 {best by test} e5 ; a line comment\n\
+                    2. Nf3 (2. f4 exf4 (2... d5) 3. Nf3) 2... Nc6! $1 3. Bb5 $14 a6 *"
+        .parse()
+        .unwrap();
+
+    assert_eq!(pgn.moves, moves("e2e4 e7e5 g1f3 b8c6 f1b5 a7a6"));
+    assert_eq!(pgn.result, GameResult::Ongoing);
+}
+
+#[test]
+fn reads_move_number_forms() {
+    let pgn: Pgn = "1.e4 1...e5 2. Nf3 2... Nc6 3.Bb5".parse().unwrap();
+    assert_eq!(pgn.moves, moves("e2e4 e7e5 g1f3 b8c6 f1b5"));
+
+    // A game starting with black to move
+    let pgn: Pgn = r#"[SetUp "1"]
+[FEN "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1"]
+
+1... e5 2. Nf3 *"#
+        .parse()
+        .unwrap();
+    assert_eq!(pgn.start_position.side_to_move(), Color::Black);
+    assert_eq!(pgn.moves, moves("e7e5 g1f3"));
+}
+
+#[test]
+fn reads_only_the_first_game() {
+    let pgn: Pgn = "1. d4 d5 1/2-1/2\n\n[Event \"Second\"]\n\n1. e4 *"
+        .parse()
+        .unwrap();
+
+    assert_eq!(pgn.moves, moves("d2d4 d7d5"));
+    assert_eq!(pgn.result, GameResult::Draw);
+}
+
+#[test]
+fn reports_the_number_of_an_invalid_move() {
+    let err = "1. e4 e5 2. Nf3 Ke7 3. Bc4 Qe8 4. Ke2 Kd9"
+        .parse::<Pgn>()
+        .unwrap_err();
+    assert_eq!(
+        err,
+        PgnError::InvalidMove {
+            move_number: 4,
+            color: Color::Black,
+            error: SanError::Invalid("Kd9".to_string()),
+        }
+    );
+    assert_eq!(err.to_string(), "move 4...: invalid move `Kd9`");
+
+    let err = "1. e4 e5 2. Ke2 Nc6 3. Nf6".parse::<Pgn>().unwrap_err();
+    assert_eq!(
+        err,
+        PgnError::InvalidMove {
+            move_number: 3,
+            color: Color::White,
+            error: SanError::Illegal("Nf6".to_string()),
+        }
+    );
+    assert_eq!(err.to_string(), "move 3.: illegal move `Nf6`");
+}
+
+#[test]
+fn reports_malformed_text() {
+    assert_eq!(
+        "1. e4 { never closed".parse::<Pgn>().unwrap_err(),
+        PgnError::UnterminatedComment
+    );
+    assert_eq!(
+        "1. e4 e5 2. Nf3 ) Nc6 3. Bb5 *".parse::<Pgn>().unwrap_err(),
+        PgnError::UnmatchedParenthesis
+    );
+    assert_eq!(
+        "1. e4 e5 (1... c5 2. Nf3 *".parse::<Pgn>().unwrap_err(),
+        PgnError::UnterminatedVariation
+    );
+    assert_eq!(
+        "[Event]\n\n1. e4 *".parse::<Pgn>().unwrap_err(),
+        PgnError::InvalidTag("Event".to_string())
+    );
+    assert_eq!(
+        "[FEN \"8/8/8 w - - 0 1\"]\n\n*".parse::<Pgn>().unwrap_err(),
+        PgnError::InvalidFen(FenError::InvalidBoard("8/8/8".to_string()))
+    );
+}
+
+#[test]
+fn writes_the_seven_tag_roster() {
+    let mut position = Position::new();
+    for uci in ["e2e4", "e7e5", "g1f3"] {
+        position.make_move(mv(uci));
+    }
+    let mut pgn = Pgn::from_position(&position, GameResult::Ongoing);
+    pgn.set_tag("White", "Alice");
+
+    assert_eq!(
+        pgn.to_string(),
+        r#"[Event "?"]
+[Site "?"]
+[Date "????.??.??"]
+[Round "?"]
+[White "Alice"]
+[Black "?"]
+[Result "*"]
+
+1. e4 e5 2. Nf3 *
+"#
+    );
+}
+
+#[test]
+fn writes_the_start_position_when_not_standard() {
+    let fen = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1";
+    let mut position = Position::from_fen(fen).unwrap();
+    for uci in ["e7e5", "g1f3", "b8c6"] {
+        position.make_move(mv(uci));
+    }
+    let pgn = Pgn::from_position(&position, GameResult::Draw);
+    let text = pgn.to_string();
+
+    assert!(text.contains(&format!(
+        "[Result \"1/2-1/2\"]\n[SetUp \"1\"]\n[FEN \"{fen}\"]\n\n"
+    )));
+    assert!(text.ends_with("\n1... e5 2. Nf3 Nc6 1/2-1/2\n"));
+
+    let parsed: Pgn = text.parse().unwrap();
+    assert_eq!(parsed.start_position.to_fen(), fen);
+    assert_eq!(parsed.moves, pgn.moves);
+    assert_eq!(parsed.result, GameResult::Draw);
+}
@@ -0,0 +1,169 @@
+use chess_core::{Move, Position, SanError};
+
+fn mv(uci: &str) -> Move {
+    uci.parse().unwrap()
+}
+
+fn from_fen(fen: &str) -> Position {
+    Position::from_fen(fen).unwrap()
+}
+
+#[test]
+fn parses_piece_and_pawn_moves() {
+    let position = Position::new();
+
+    assert_eq!(position.parse_san("e4"), Ok(mv("e2e4")));
+    assert_eq!(position.parse_san("Nf3"), Ok(mv("g1f3")));
+    assert_eq!(position.parse_san("Nf3+!?"), Ok(mv("g1f3")));
+}
+
+#[test]
+fn parses_captures() {
+    let position = from_fen("4k3/8/8/3p4/4P3/5N2/8/4K3 w - - 0 1");
+
+    assert_eq!(position.parse_san("exd5"), Ok(mv("e4d5")));
+    assert_eq!(
+        position.parse_san("Nxd5"),
+        Err(SanError::Illegal("Nxd5".to_string()))
+    );
+
+    let en_passant = from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1");
+    assert_eq!(en_passant.parse_san("exd6"), Ok(mv("e5d6")));
+}
+
+#[test]
+fn pawn_captures_need_the_origin_file() {
+    let position = from_fen("4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1");
+
+    assert_eq!(
+        position.parse_san("d5"),
+        Err(SanError::Illegal("d5".to_string()))
+    );
+    assert_eq!(
+        position.parse_san("xd5"),
+        Err(SanError::Illegal("xd5".to_string()))
+    );
+}
+
+#[test]
+fn parses_castling() {
+    let position = from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+
+    assert_eq!(position.parse_san("O-O"), Ok(mv("e1g1")));
+    assert_eq!(position.parse_san("0-0-0"), Ok(mv("e1c1")));
+
+    let position = position.to_fen().replace("KQkq", "kq");
+    assert_eq!(
+        Position::from_fen(&position).unwrap().parse_san("O-O"),
+        Err(SanError::Illegal("O-O".to_string()))
+    );
+}
+
+#[test]
+fn parses_promotions() {
+    let position = from_fen("3r3k/4P3/8/8/8/8/8/4K3 w - - 0 1");
+
+    assert_eq!(position.parse_san("e8=Q+"), Ok(mv("e7e8q")));
+    assert_eq!(position.parse_san("e8N"), Ok(mv("e7e8n")));
+    assert_eq!(position.parse_san("exd8=R"), Ok(mv("e7d8r")));
+    assert_eq!(
+        position.parse_san("e8"),
+        Err(SanError::Illegal("e8".to_string()))
+    );
+    assert_eq!(
+        position.parse_san("e8=X"),
+        Err(SanError::Invalid("e8=X".to_string()))
+    );
+}
+
+#[test]
+fn parses_disambiguated_moves() {
+    // Knights on b1 and f1 reach d2, rooks on a1 and a5 reach a3
+    let position = from_fen("4k3/8/8/R7/8/8/8/RN2KN2 w - - 0 1");
+
+    assert_eq!(position.parse_san("Nbd2"), Ok(mv("b1d2")));
+    assert_eq!(position.parse_san("Nfd2"), Ok(mv("f1d2")));
+    assert_eq!(position.parse_san("R1a3"), Ok(mv("a1a3")));
+    assert_eq!(position.parse_san("R5a3"), Ok(mv("a5a3")));
+    assert_eq!(position.parse_san("Nb1d2"), Ok(mv("b1d2")));
+    assert_eq!(
+        position.parse_san("Nd2"),
+        Err(SanError::Ambiguous("Nd2".to_string()))
+    );
+    assert_eq!(
+        position.parse_san("Ra3"),
+        Err(SanError::Ambiguous("Ra3".to_string()))
+    );
+}
+
+#[test]
+fn rejects_malformed_moves() {
+    let position = Position::new();
+
+    for san in ["", "e", "e9", "Xe4", "Nf3f", "N1a2b3"] {
+        assert_eq!(
+            position.parse_san(san),
+            Err(SanError::Invalid(san.to_string())),
+            "{san}"
+        );
+    }
+    assert_eq!(
+        position.parse_san("e5"),
+        Err(SanError::Illegal("e5".to_string()))
+    );
+}
+
+#[test]
+fn writes_moves() {
+    let position = from_fen("4k3/8/8/3p4/4P3/5N2/8/4K3 w - - 0 1");
+
+    assert_eq!(position.to_san(mv("e4e5")), "e5");
+    assert_eq!(position.to_san(mv("e4d5")), "exd5");
+    assert_eq!(position.to_san(mv("f3d4")), "Nd4");
+    assert_eq!(position.to_san(mv("f3d2")), "Nd2");
+}
+
+#[test]
+fn writes_disambiguation() {
+    let position = from_fen("4k3/8/8/R7/8/8/8/RN2KN2 w - - 0 1");
+    assert_eq!(position.to_san(mv("b1d2")), "Nbd2");
+    assert_eq!(position.to_san(mv("f1d2")), "Nfd2");
+    assert_eq!(position.to_san(mv("a1a3")), "R1a3");
+    assert_eq!(position.to_san(mv("a5a3")), "R5a3");
+
+    // Queens on a1 and a3 share a file, queens on a1 and c1 a rank
+    let position = from_fen("4k3/8/8/8/8/Q7/8/Q1Q1K3 w - - 0 1");
+    assert_eq!(position.to_san(mv("a1b2")), "Qa1b2");
+    assert_eq!(position.to_san(mv("a3b2")), "Q3b2");
+    assert_eq!(position.to_san(mv("c1b2")), "Qcb2");
+}
+
+#[test]
+fn writes_promotions_checks_and_mates() {
+    let position = from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1");
+    assert_eq!(position.to_san(mv("a7a8q")), "a8=Q+");
+    assert_eq!(position.to_san(mv("a7a8n")), "a8=N");
+
+    let position = from_fen("6k1/5ppp/8/8/8/8/8/R3K3 w - - 0 1");
+    assert_eq!(position.to_san(mv("a1a8")), "Ra8#");
+}
+
+#[test]
+fn writes_castling() {
+    let position = from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+
+    assert_eq!(position.to_san(mv("e1g1")), "O-O");
+    assert_eq!(position.to_san(mv("e1c1")), "O-O-O");
+}
+
+#[test]
+fn written_moves_are_parsed_back() {
+    let mut position = Position::new();
+    for uci in [
+        "e2e4", "d7d5", "e4d5", "g8f6", "f1b5", "c7c6", "d5c6", "d8d7",
+    ] {
+        let san = position.to_san(mv(uci));
+        assert_eq!(position.parse_san(&san), Ok(mv(uci)), "{san}");
+        position.make_move(mv(uci));
+    }
+}
@@ -0,0 +1,84 @@
+use std::sync::atomic::AtomicBool;
+
+use chess_core::{search, Move, PieceType, Position, SearchLimits, SearchResult, MATE_SCORE};
+
+fn mv(uci: &str) -> Move {
+    uci.parse().unwrap()
+}
+
+fn search_depth(fen: &str, depth: u32) -> SearchResult {
+    let position = Position::from_fen(fen).unwrap();
+    search(
+        &position,
+        SearchLimits::depth(depth),
+        &AtomicBool::new(false),
+        |_| {},
+    )
+}
+
+#[test]
+fn finds_mate_in_one() {
+    let result = search_depth("6k1/5ppp/8/8/8/8/8/R3K3 w - - 0 1", 3);
+
+    assert_eq!(result.best_move, Some(mv("a1a8")));
+    assert_eq!(result.score, MATE_SCORE - 1);
+}
+
+#[test]
+fn finds_mate_in_two() {
+    // 1. Rb7 or Ra7 cuts off the seventh rank, mating on the eighth next
+    let fen = "7k/8/8/8/8/8/R7/1R2K3 w - - 0 1";
+    let result = search_depth(fen, 4);
+
+    assert_eq!(result.score, MATE_SCORE - 3);
+
+    let mut position = Position::from_fen(fen).unwrap();
+    position.make_move(result.best_move.unwrap());
+    for reply in position.legal_moves() {
+        position.make_move(reply);
+        let mate = search(
+            &position,
+            SearchLimits::depth(2),
+            &AtomicBool::new(false),
+            |_| {},
+        );
+        assert_eq!(mate.score, MATE_SCORE - 1, "after {reply}");
+        position.unmake_move();
+    }
+}
+
+#[test]
+fn stops_at_the_given_depth() {
+    let result = search_depth("4k3/8/8/8/8/8/8/R3K3 w - - 0 1", 2);
+
+    assert_eq!(result.depth, 2);
+    assert!(result.best_move.is_some());
+}
+
+#[test]
+fn reports_no_move_when_mated() {
+    let result = search_depth("R5k1/5ppp/8/8/8/8/8/4K3 b - - 0 1", 3);
+
+    assert_eq!(result.best_move, None);
+    assert_eq!(result.score, -MATE_SCORE);
+}
+
+#[test]
+fn does_not_hang_a_piece() {
+    // The pawn on d5 is defended
+    let result = search_depth("4k3/8/2p5/3p4/8/8/8/3QK3 w - - 0 1", 3);
+    assert_ne!(result.best_move, Some(mv("d1d5")));
+    assert!(result.score > 0);
+
+    // The queen is attacked and must not be left en prise
+    let fen = "4k3/8/8/8/2p5/3Q4/8/4K3 w - - 0 1";
+    let result = search_depth(fen, 3);
+    assert!(result.score > 0);
+
+    let mut position = Position::from_fen(fen).unwrap();
+    position.make_move(result.best_move.unwrap());
+    for reply in position.legal_moves() {
+        let captured = position.piece_at(reply.to).map(|piece| piece.piece_type);
+        assert_ne!(captured, Some(PieceType::Queen), "after {reply}");
+    }
+}
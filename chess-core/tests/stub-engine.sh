@@ -0,0 +1,36 @@
+# A scripted UCI engine for the client tests: it answers 1. e4 with e5 and
+# plays e4 from any other position. Infinite searches last until stopped.
+position=""
+searching=""
+while read -r command; do
+    case "$command" in
+        uci)
+            echo "id name Stub"
+            echo "id author Tests"
+            echo "option name Hash type spin default 16 min 1 max 1024"
+            echo "uciok"
+            ;;
+        isready) echo "readyok" ;;
+        "position startpos moves e2e4") position="e4" ;;
+        position*) position="" ;;
+        "go infinite") searching="yes" ;;
+        stop)
+            if [ -n "$searching" ]; then
+                searching=""
+                echo "bestmove e2e4"
+            fi
+            ;;
+        go*)
+            if [ "$position" = "e4" ]; then
+                echo "info depth 1 score cp 10 nodes 20 time 1 pv e7e5"
+                echo "info depth 2 score cp -5 nodes 400 time 2 pv e7e5 g1f3"
+                echo "bestmove e7e5 ponder g1f3"
+            else
+                echo "info string thinking about $command"
+                echo "info depth 3 score mate 2 pv e2e4"
+                echo "bestmove e2e4"
+            fi
+            ;;
+        quit) exit 0 ;;
+    esac
+done
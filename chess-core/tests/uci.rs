@@ -0,0 +1,128 @@
+use std::{process::Command, thread, time::Duration};
+
+use chess_core::{
+    uci::{position_command, Info, Score, UciEngine, UciError},
+    Move, Position, SearchLimits, Square,
+};
+
+fn stub_engine() -> UciEngine {
+    let mut command = Command::new("sh");
+    command.arg(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/stub-engine.sh"));
+    UciEngine::spawn(command).unwrap()
+}
+
+fn mv(uci: &str) -> Move {
+    uci.parse().unwrap()
+}
+
+#[test]
+fn handshake_reads_engine_id() {
+    let engine = stub_engine();
+
+    assert_eq!(engine.name.as_deref(), Some("Stub"));
+    assert_eq!(engine.author.as_deref(), Some("Tests"));
+}
+
+#[test]
+fn go_returns_best_move_and_info() {
+    let mut engine = stub_engine();
+    let mut position = Position::new();
+    position.make_move(mv("e2e4"));
+
+    let mut infos = vec![];
+    let best_move = engine
+        .go(&position, SearchLimits::depth(2), |info| {
+            infos.push(info.clone())
+        })
+        .unwrap();
+
+    assert_eq!(best_move.best_move, Some(mv("e7e5")));
+    assert_eq!(best_move.ponder, Some(mv("g1f3")));
+    assert_eq!(infos.len(), 2);
+    assert_eq!(infos[1].depth, Some(2));
+    assert_eq!(infos[1].score, Some(Score::Centipawns(-5)));
+    assert_eq!(infos[1].pv, vec![mv("e7e5"), mv("g1f3")]);
+}
+
+#[test]
+fn engine_can_search_several_positions() {
+    let mut engine = stub_engine();
+    let limits = SearchLimits::time(Duration::from_millis(10));
+
+    let first = engine.go(&Position::new(), limits, |_| {}).unwrap();
+    engine.new_game().unwrap();
+    let second = engine.go(&Position::new(), limits, |_| {}).unwrap();
+
+    assert_eq!(first.best_move, Some(mv("e2e4")));
+    assert_eq!(second.best_move, Some(mv("e2e4")));
+}
+
+#[test]
+fn infinite_search_runs_until_stopped() {
+    let mut engine = stub_engine();
+    let stop = engine.stop_handle();
+
+    let stopper = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        stop.stop().unwrap();
+    });
+    let best_move = engine
+        .go(&Position::new(), SearchLimits::default(), |_| {})
+        .unwrap();
+    stopper.join().unwrap();
+
+    assert_eq!(best_move.best_move, Some(mv("e2e4")));
+}
+
+#[test]
+fn missing_engine_is_an_error() {
+    let result = UciEngine::spawn(Command::new("./no-such-engine"));
+
+    assert!(matches!(result, Err(UciError::Io(_))));
+}
+
+#[test]
+fn position_command_lists_moves() {
+    let mut position = Position::new();
+    assert_eq!(position_command(&position), "position startpos");
+
+    position.make_move(mv("e2e4"));
+    position.make_move(mv("c7c5"));
+    assert_eq!(
+        position_command(&position),
+        "position startpos moves e2e4 c7c5"
+    );
+
+    let position = Position::from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(
+        position_command(&position),
+        "position fen 4k3/P7/8/8/8/8/8/4K3 w - - 0 1"
+    );
+}
+
+#[test]
+fn info_parses_known_fields() {
+    let info =
+        Info::parse("info depth 12 seldepth 18 score mate -3 nodes 123456 time 250 pv e2e4 e7e5");
+
+    assert_eq!(info.depth, Some(12));
+    assert_eq!(info.score, Some(Score::Mate(-3)));
+    assert_eq!(info.nodes, Some(123_456));
+    assert_eq!(info.time, Some(250));
+    assert_eq!(info.pv.len(), 2);
+    assert_eq!(
+        info.to_string(),
+        "info depth 12 score mate -3 nodes 123456 time 250 pv e2e4 e7e5"
+    );
+}
+
+#[test]
+fn moves_parse_from_coordinate_notation() {
+    let promotion = mv("a7a8n");
+
+    assert_eq!(promotion.from, Square::new(0, 6));
+    assert_eq!(promotion.to, Square::new(0, 7));
+    assert_eq!(promotion.to_string(), "a7a8n");
+    assert!("a7a8k".parse::<Move>().is_err());
+    assert!("e2".parse::<Move>().is_err());
+}
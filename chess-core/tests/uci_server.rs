@@ -0,0 +1,164 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    process::{Command, Stdio},
+    time::Duration,
+};
+
+use chess_core::{
+    uci::{Score, UciEngine},
+    Move, Position, SearchLimits,
+};
+
+fn engine() -> UciEngine {
+    UciEngine::spawn(Command::new(env!("CARGO_BIN_EXE_bevy-chess-uci"))).unwrap()
+}
+
+fn mv(uci: &str) -> Move {
+    uci.parse().unwrap()
+}
+
+#[test]
+fn engine_identifies_itself() {
+    assert_eq!(engine().name.as_deref(), Some("bevy-chess"));
+}
+
+#[test]
+fn go_answers_with_a_legal_move() {
+    let mut engine = engine();
+    let mut position = Position::new();
+    position.make_move(mv("e2e4"));
+    position.make_move(mv("e7e5"));
+
+    let mut depths = vec![];
+    let best_move = engine
+        .go(&position, SearchLimits::depth(3), |info| {
+            depths.extend(info.depth)
+        })
+        .unwrap();
+
+    assert_eq!(depths, vec![1, 2, 3]);
+    assert!(position.is_legal(best_move.best_move.unwrap()));
+}
+
+#[test]
+fn go_finds_mate_in_one() {
+    let mut engine = engine();
+    let position = Position::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+
+    let mut score = None;
+    let best_move = engine
+        .go(
+            &position,
+            SearchLimits::time(Duration::from_secs(5)),
+            |info| score = info.score,
+        )
+        .unwrap();
+
+    assert_eq!(best_move.best_move, Some(mv("a1a8")));
+    assert_eq!(score, Some(Score::Mate(1)));
+}
+
+#[test]
+fn go_without_moves_answers_null_move() {
+    let mut engine = engine();
+    let position = Position::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+
+    let best_move = engine
+        .go(&position, SearchLimits::depth(2), |_| {})
+        .unwrap();
+
+    assert_eq!(best_move.best_move, None);
+}
+
+#[test]
+fn stop_ends_an_infinite_search() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_bevy-chess-uci"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    let stdout = BufReader::new(child.stdout.take().unwrap());
+
+    writeln!(stdin, "position startpos moves e2e4\ngo infinite").unwrap();
+    std::thread::sleep(Duration::from_millis(200));
+    writeln!(stdin, "isready\nstop\nquit").unwrap();
+
+    let lines: Vec<String> = stdout.lines().map(Result::unwrap).collect();
+    assert!(child.wait().unwrap().success());
+    assert!(lines.iter().any(|line| line == "readyok"));
+
+    let best_move = lines.iter().find_map(|line| line.strip_prefix("bestmove "));
+    let mut position = Position::new();
+    position.make_move(mv("e2e4"));
+    assert!(position.is_legal(mv(best_move.unwrap())));
+}
+
+#[test]
+fn infinite_search_waits_for_stop_after_finding_mate() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_bevy-chess-uci"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    let stdout = BufReader::new(child.stdout.take().unwrap());
+
+    writeln!(
+        stdin,
+        "position fen 6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1\ngo infinite"
+    )
+    .unwrap();
+    std::thread::sleep(Duration::from_millis(200));
+    writeln!(stdin, "isready\nstop\nquit").unwrap();
+
+    let lines: Vec<String> = stdout.lines().map(Result::unwrap).collect();
+    assert!(child.wait().unwrap().success());
+
+    let ready = lines.iter().position(|line| line == "readyok").unwrap();
+    let best_move = lines
+        .iter()
+        .position(|line| line.starts_with("bestmove"))
+        .unwrap();
+    assert!(ready < best_move, "{lines:?}");
+    assert_eq!(lines[best_move], "bestmove a1a8");
+}
+
+#[test]
+fn go_perft_counts_nodes() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_bevy-chess-uci"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    writeln!(
+        child.stdin.take().unwrap(),
+        "position fen r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1\ngo perft 2\nquit"
+    )
+    .unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    let output = String::from_utf8(output.stdout).unwrap();
+    assert!(output.contains("e5f7: 44"));
+    assert!(output.contains("Nodes searched: 2039"));
+}
+
+#[test]
+fn invalid_position_is_reported() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_bevy-chess-uci"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    writeln!(
+        child.stdin.take().unwrap(),
+        "position startpos moves e2e5\nquit"
+    )
+    .unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    let output = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(output.trim(), "info string illegal move `e2e5`");
+}
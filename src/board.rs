@@ -1,14 +1,90 @@
+use std::f32::consts::PI;
+
 use bevy::{
+    ecs::system::SystemParam,
     prelude::*,
-    sprite::{MaterialMesh2dBundle, Mesh2dHandle},
+    sprite::{Anchor, MaterialMesh2dBundle, Mesh2dHandle},
+    window::PrimaryWindow,
 };
+use chess_core::Square;
+
+use crate::game::{Game, GameMode};
+
+/// A square of the board, which move targets are marked on.
+#[derive(Component)]
+pub struct Tile {
+    pub square: Square,
+}
+
+/// Side of the board shown at the bottom of the window.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Orientation {
+    #[default]
+    White,
+    Black,
+}
+
+impl From<chess_core::Color> for Orientation {
+    fn from(color: chess_core::Color) -> Self {
+        match color {
+            chess_core::Color::White => Orientation::White,
+            chess_core::Color::Black => Orientation::Black,
+        }
+    }
+}
 
+/// How the orientation is chosen, cycled with O.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OrientationMode {
+    #[default]
+    White,
+    Black,
+    /// Shows the board from the side of the human player to move, so both
+    /// players of a game on one machine see it from their own side.
+    AutoFlip,
+}
+
+/// Keeps a sprite upright when the board is shown from black's side.
 #[derive(Component)]
-struct Tile;
+pub struct Upright;
+
+/// Cursor position translated into board coordinates through the camera.
+#[derive(SystemParam)]
+pub struct BoardCursor<'w, 's> {
+    windows_query: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+    camera_query: Query<'w, 's, (&'static Camera, &'static GlobalTransform)>,
+}
+
+impl BoardCursor<'_, '_> {
+    pub fn world_position(&self) -> Option<Vec2> {
+        let (camera, camera_transform) = self.camera_query.get_single().ok()?;
+        let cursor_position = self.windows_query.get_single().ok()?.cursor_position()?;
+
+        camera.viewport_to_world_2d(camera_transform, cursor_position)
+    }
+
+    pub fn square(&self) -> Option<Square> {
+        self.world_position().and_then(get_square_from_pixel)
+    }
+}
 
 pub const TILE_SIZE: f32 = 100.0;
 pub const HALF_TILE: f32 = 50.0;
 
+pub const DARK_SQUARE: Color = Color::linear_rgb(187.0 / 255.0, 190.0 / 255.0, 100.0 / 255.0);
+pub const LIGHT_SQUARE: Color = Color::linear_rgb(234.0 / 255.0, 240.0 / 255.0, 206.0 / 255.0);
+
+/// Distance of coordinate labels from the corner of their tile.
+const LABEL_MARGIN: f32 = 6.0;
+
+/// A file letter or rank number in the corner of a tile along the edge of
+/// the board.
+#[derive(Component, Clone, Copy, Debug)]
+pub enum Coordinate {
+    File(u8),
+    Rank(u8),
+}
+
 pub fn setup_board(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -16,18 +92,8 @@ pub fn setup_board(
 ) {
     let mesh = meshes.add(Rectangle::new(TILE_SIZE, TILE_SIZE));
 
-    // Distribute colors evenly across the rainbow.
-    let dark_material = materials.add(Color::linear_rgb(
-        187.0 / 255.0,
-        190.0 / 255.0,
-        100.0 / 255.0,
-    ));
-
-    let light_material = materials.add(Color::linear_rgb(
-        234.0 / 255.0,
-        240.0 / 255.0,
-        206.0 / 255.0,
-    ));
+    let dark_material = materials.add(DARK_SQUARE);
+    let light_material = materials.add(LIGHT_SQUARE);
 
     for i in 0..8 {
         for j in 0..8 {
@@ -42,7 +108,9 @@ pub fn setup_board(
             let transform = Transform::from_xyz(x, y, 0.0);
 
             commands.spawn((
-                Tile,
+                Tile {
+                    square: Square::new(i, j),
+                },
                 MaterialMesh2dBundle {
                     mesh: Mesh2dHandle(mesh.clone()),
                     material,
@@ -54,6 +122,67 @@ pub fn setup_board(
     }
 }
 
+/// Labels files along the bottom edge of the board and ranks along the left
+/// one, in the color of the opposite squares to stand out on their tile.
+pub fn setup_coordinates(mut commands: Commands) {
+    for i in 0..8 {
+        for coordinate in [Coordinate::File(i), Coordinate::Rank(i)] {
+            let (label, anchor) = match coordinate {
+                Coordinate::File(file) => ((b'a' + file) as char, Anchor::BottomRight),
+                Coordinate::Rank(rank) => ((b'1' + rank) as char, Anchor::TopLeft),
+            };
+
+            commands.spawn((
+                coordinate,
+                Upright,
+                Text2dBundle {
+                    text: Text::from_section(
+                        label,
+                        TextStyle {
+                            font_size: 20.0,
+                            ..default()
+                        },
+                    ),
+                    text_anchor: anchor,
+                    ..default()
+                },
+            ));
+        }
+    }
+}
+
+/// Moves the coordinate labels to the edges shown at the bottom and left of
+/// the window, i.e. the top and right ones in board space from black's side.
+pub fn place_coordinates(
+    orientation: Res<Orientation>,
+    mut label_query: Query<(Ref<Coordinate>, &mut Transform, &mut Text)>,
+) {
+    for (coordinate, mut transform, mut text) in &mut label_query {
+        if !orientation.is_changed() && !coordinate.is_added() {
+            continue;
+        }
+
+        // The tile the label is on and its corner, as seen on screen
+        let (file, rank, corner) = match (*coordinate, *orientation) {
+            (Coordinate::File(file), Orientation::White) => (file, 0, Vec2::new(1.0, -1.0)),
+            (Coordinate::File(file), Orientation::Black) => (file, 7, Vec2::new(-1.0, 1.0)),
+            (Coordinate::Rank(rank), Orientation::White) => (0, rank, Vec2::new(-1.0, 1.0)),
+            (Coordinate::Rank(rank), Orientation::Black) => (7, rank, Vec2::new(1.0, -1.0)),
+        };
+
+        let center = get_pixels_by_pos(IVec2::new(file as i32, rank as i32));
+        let position = center + corner * (HALF_TILE - LABEL_MARGIN);
+        transform.translation = position.extend(0.6);
+
+        text.sections[0].style.color = match (file + rank) % 2 == 0 {
+            true => LIGHT_SQUARE,
+            false => DARK_SQUARE,
+        };
+    }
+}
+
+/// Center of a tile in board space, with a1 at the origin whichever side is
+/// shown at the bottom. The camera turns the board, not its coordinates.
 pub fn get_pixels_by_pos(pos: IVec2) -> Vec2 {
     Vec2::new(
         (pos.x as f32) * TILE_SIZE + HALF_TILE,
@@ -62,8 +191,83 @@ pub fn get_pixels_by_pos(pos: IVec2) -> Vec2 {
 }
 
 pub fn get_pos_from_pixel(pixel_pos: Vec2) -> IVec2 {
-    let x = (pixel_pos.x / 100.0) as i32;
-    let y = 7 - (pixel_pos.y / 100.0) as i32;
+    let x = (pixel_pos.x / TILE_SIZE).floor() as i32;
+    let y = (pixel_pos.y / TILE_SIZE).floor() as i32;
 
     IVec2::new(x, y)
 }
+
+pub fn get_pixels_by_square(square: Square) -> Vec2 {
+    get_pixels_by_pos(IVec2::new(square.file() as i32, square.rank() as i32))
+}
+
+pub fn get_square_from_pixel(pixel_pos: Vec2) -> Option<Square> {
+    let pos = get_pos_from_pixel(pixel_pos);
+    Square::from_coords(pos.x, pos.y)
+}
+
+/// Turns the camera around the center of the board to show it from the
+/// oriented side, rotating sprites with it so they stay upright.
+pub fn orient_board(
+    orientation: Res<Orientation>,
+    mut camera_query: Query<&mut Transform, (With<Camera>, Without<Upright>)>,
+    mut upright_query: Query<(Ref<Upright>, &mut Transform), Without<Camera>>,
+) {
+    let rotation = match *orientation {
+        Orientation::White => Quat::IDENTITY,
+        Orientation::Black => Quat::from_rotation_z(PI),
+    };
+
+    if orientation.is_changed() {
+        for mut transform in &mut camera_query {
+            transform.rotation = rotation;
+        }
+    }
+
+    for (upright, mut transform) in &mut upright_query {
+        if orientation.is_changed() || upright.is_added() {
+            transform.rotation = rotation;
+        }
+    }
+}
+
+pub fn cycle_orientation_mode(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut orientation_mode: ResMut<OrientationMode>,
+) {
+    if !keys.just_pressed(KeyCode::KeyO) {
+        return;
+    }
+
+    *orientation_mode = match *orientation_mode {
+        OrientationMode::White => OrientationMode::Black,
+        OrientationMode::Black => OrientationMode::AutoFlip,
+        OrientationMode::AutoFlip => OrientationMode::White,
+    };
+    info!("Board orientation: {:?}", *orientation_mode);
+}
+
+/// Applies the orientation mode, turning the board towards the side to
+/// move after each move when flipping automatically.
+pub fn follow_orientation_mode(
+    orientation_mode: Res<OrientationMode>,
+    game: Res<Game>,
+    game_mode: Res<GameMode>,
+    mut orientation: ResMut<Orientation>,
+) {
+    if !orientation_mode.is_changed() && !game.is_changed() {
+        return;
+    }
+
+    let new_orientation = match *orientation_mode {
+        OrientationMode::White => Orientation::White,
+        OrientationMode::Black => Orientation::Black,
+        // Against the engine or over the network, the human stays at the bottom
+        OrientationMode::AutoFlip if game_mode.is_human(game.side_to_move()) => {
+            game.side_to_move().into()
+        }
+        OrientationMode::AutoFlip => return,
+    };
+
+    orientation.set_if_neq(new_orientation);
+}
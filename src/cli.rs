@@ -0,0 +1,197 @@
+use std::{fmt, path::PathBuf, process, time::Duration};
+
+use chess_core::{Color, TimeControl};
+
+use crate::{board::TILE_SIZE, engine::EngineLimits, game::GameMode};
+
+const USAGE: &str = "\
+Usage: bevy-chess [OPTIONS]
+
+Options:
+  --fen <FEN>           Start from the given position
+  --pgn <FILE>          Replay the game stored in a PGN file, using the
+                        arrow keys, Home and End to step through it
+  --flip                Show the board from black's side
+  --auto-flip           Turn the board towards the side to move after each move
+  --tile-size <PIXELS>  Initial size of each board tile, scaled with the window [default: 100]
+  --mode <MODE>         human-human, human-engine or engine-engine [default: human-human]
+  --depth <PLIES>       Let the engine search up to the given depth
+  --host <ADDRESS>      Wait for another player to join on the given address, e.g. 0.0.0.0:7878
+  --join <ADDRESS>      Join a game hosted on the given address
+  --engine <PATH>       Play against an external UCI engine instead of the built-in one
+  --move-time <MS>      Let the engine think for the given time per move [default: 1000]
+  --time <CONTROL>      Play with clocks: minutes per side, optionally with seconds of
+                        increment or delay per move, e.g. 5, 3+2 or 5d3
+  -h, --help            Print this help";
+
+/// Options the game was launched with.
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub fen: Option<String>,
+    pub pgn: Option<PathBuf>,
+    pub flip: bool,
+    pub auto_flip: bool,
+    pub tile_size: f32,
+    pub mode: GameMode,
+    pub engine_limits: EngineLimits,
+    pub engine: Option<PathBuf>,
+    pub host: Option<String>,
+    pub join: Option<String>,
+    pub time_control: Option<TimeControl>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            fen: None,
+            pgn: None,
+            flip: false,
+            auto_flip: false,
+            tile_size: TILE_SIZE,
+            mode: GameMode::HumanVsHuman,
+            engine_limits: EngineLimits::default(),
+            engine: None,
+            host: None,
+            join: None,
+            time_control: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum CliError {
+    Help,
+    MissingValue(String),
+    InvalidValue(String, String),
+    UnknownOption(String),
+    Conflict(String, &'static str),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Help => write!(f, "{USAGE}"),
+            CliError::MissingValue(option) => write!(f, "missing value for `{option}`"),
+            CliError::InvalidValue(option, value) => {
+                write!(f, "invalid value `{value}` for `{option}`")
+            }
+            CliError::UnknownOption(option) => write!(f, "unknown option `{option}`"),
+            CliError::Conflict(option, other) => {
+                write!(f, "`{option}` cannot be used with `{other}`")
+            }
+        }
+    }
+}
+
+impl Options {
+    /// Parses the process arguments, exiting with the usage on errors.
+    pub fn from_env() -> Options {
+        match Options::parse(std::env::args().skip(1)) {
+            Ok(options) => options,
+            Err(CliError::Help) => {
+                println!("{USAGE}");
+                process::exit(0);
+            }
+            Err(err) => {
+                eprintln!("error: {err}\n\n{USAGE}");
+                process::exit(2);
+            }
+        }
+    }
+
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Options, CliError> {
+        let mut options = Options::default();
+        // Network games choose the mode, so it cannot be given with them
+        let mut mode_given = false;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--fen" if options.pgn.is_some() => return Err(CliError::Conflict(arg, "--pgn")),
+                "--fen" if options.join.is_some() => return Err(CliError::Conflict(arg, "--join")),
+                "--fen" => options.fen = Some(next_value(&mut args, &arg)?),
+                "--pgn" if options.fen.is_some() => return Err(CliError::Conflict(arg, "--fen")),
+                "--pgn" if options.join.is_some() => return Err(CliError::Conflict(arg, "--join")),
+                "--pgn" if options.host.is_some() => return Err(CliError::Conflict(arg, "--host")),
+                "--pgn" => options.pgn = Some(PathBuf::from(next_value(&mut args, &arg)?)),
+                "--flip" => options.flip = true,
+                "--auto-flip" => options.auto_flip = true,
+                "--tile-size" => {
+                    let tile_size = next_value(&mut args, &arg)?;
+                    options.tile_size = match tile_size.parse::<f32>() {
+                        Ok(size) if size >= 10.0 => size,
+                        _ => return Err(CliError::InvalidValue(arg, tile_size)),
+                    };
+                }
+                "--mode" if options.host.is_some() => {
+                    return Err(CliError::Conflict(arg, "--host"))
+                }
+                "--mode" if options.join.is_some() => {
+                    return Err(CliError::Conflict(arg, "--join"))
+                }
+                "--mode" => {
+                    mode_given = true;
+                    let mode = next_value(&mut args, &arg)?;
+                    options.mode = match mode.as_str() {
+                        "human-human" => GameMode::HumanVsHuman,
+                        "human-engine" => GameMode::HumanVsEngine {
+                            human: Color::White,
+                        },
+                        "engine-engine" => GameMode::EngineVsEngine,
+                        _ => return Err(CliError::InvalidValue(arg, mode)),
+                    };
+                }
+                "--host" if options.join.is_some() => {
+                    return Err(CliError::Conflict(arg, "--join"))
+                }
+                "--host" if mode_given => return Err(CliError::Conflict(arg, "--mode")),
+                "--host" if options.pgn.is_some() => return Err(CliError::Conflict(arg, "--pgn")),
+                "--host" => options.host = Some(next_value(&mut args, &arg)?),
+                "--join" if options.host.is_some() => {
+                    return Err(CliError::Conflict(arg, "--host"))
+                }
+                "--join" if mode_given => return Err(CliError::Conflict(arg, "--mode")),
+                "--join" if options.fen.is_some() => return Err(CliError::Conflict(arg, "--fen")),
+                "--join" if options.pgn.is_some() => return Err(CliError::Conflict(arg, "--pgn")),
+                "--join" => options.join = Some(next_value(&mut args, &arg)?),
+                "--engine" => options.engine = Some(PathBuf::from(next_value(&mut args, &arg)?)),
+                "--depth" => {
+                    let depth = next_value(&mut args, &arg)?;
+                    options.engine_limits.depth = match depth.parse::<u32>() {
+                        Ok(depth) if depth >= 1 => Some(depth),
+                        _ => return Err(CliError::InvalidValue(arg, depth)),
+                    };
+                }
+                "--move-time" => {
+                    let time = next_value(&mut args, &arg)?;
+                    options.engine_limits.time = match time.parse::<u64>() {
+                        Ok(millis) if millis >= 1 => Some(Duration::from_millis(millis)),
+                        _ => return Err(CliError::InvalidValue(arg, time)),
+                    };
+                }
+                "--time" => {
+                    let time_control = next_value(&mut args, &arg)?;
+                    options.time_control = match time_control.parse() {
+                        Ok(time_control) => Some(time_control),
+                        Err(_) => return Err(CliError::InvalidValue(arg, time_control)),
+                    };
+                }
+                "-h" | "--help" => return Err(CliError::Help),
+                _ => return Err(CliError::UnknownOption(arg)),
+            }
+        }
+
+        // Against the engine, the human plays the side shown at the bottom
+        if let GameMode::HumanVsEngine { human } = &mut options.mode {
+            if options.flip {
+                *human = Color::Black;
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+fn next_value(args: &mut impl Iterator<Item = String>, option: &str) -> Result<String, CliError> {
+    args.next()
+        .ok_or_else(|| CliError::MissingValue(option.to_string()))
+}
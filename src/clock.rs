@@ -0,0 +1,191 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use chess_core::{ChessClock, Color, DrawReason};
+
+use crate::{
+    board::{Orientation, Upright, TILE_SIZE},
+    game::{update_game_state, Game, GameState},
+    replay::Replay,
+};
+
+pub struct ClockPlugin;
+
+impl Plugin for ClockPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_clocks.run_if(resource_exists::<Clock>))
+            .add_systems(
+                Update,
+                (press_clock, tick_clock)
+                    .chain()
+                    .run_if(resource_exists::<Clock>.and_then(not(resource_exists::<Replay>))),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    flag_fall.after(update_game_state),
+                    layout_clocks,
+                    update_clock_text,
+                )
+                    .run_if(resource_exists::<Clock>),
+            );
+    }
+}
+
+/// Width of the panel the clocks are shown in, to the right of the board.
+pub const PANEL_WIDTH: f32 = TILE_SIZE * 2.0;
+
+const FONT_SIZE: f32 = 40.0;
+const IDLE_COLOR: bevy::color::Color = bevy::color::Color::srgb(0.5, 0.5, 0.5);
+const RUNNING_COLOR: bevy::color::Color = bevy::color::Color::WHITE;
+const FLAGGED_COLOR: bevy::color::Color = bevy::color::Color::srgb(0.9, 0.2, 0.2);
+
+/// The clocks of both players, only present when playing with a time control.
+#[derive(Resource, Clone, Debug, Deref, DerefMut)]
+pub struct Clock {
+    #[deref]
+    clock: ChessClock,
+    /// Number of moves played when the clock was last pressed.
+    moves: usize,
+}
+
+impl Clock {
+    pub fn new(clock: ChessClock, game: &Game) -> Clock {
+        Clock {
+            clock,
+            moves: game.moves().count(),
+        }
+    }
+}
+
+#[derive(Component)]
+struct ClockText(Color);
+
+fn setup_clocks(mut commands: Commands) {
+    for color in [Color::White, Color::Black] {
+        commands.spawn((
+            ClockText(color),
+            Upright,
+            Text2dBundle {
+                text: Text::from_section(
+                    "",
+                    TextStyle {
+                        font_size: FONT_SIZE,
+                        color: IDLE_COLOR,
+                        ..default()
+                    },
+                ),
+                ..default()
+            },
+        ));
+    }
+}
+
+/// Ends the turn of the player who just moved. Moves taken back, e.g. by an
+/// undo, do not give any time back.
+fn press_clock(game: Res<Game>, mut clock: ResMut<Clock>) {
+    let moves = game.moves().count();
+    if moves > clock.moves {
+        clock.press(game.side_to_move().opposite());
+    }
+    if moves != clock.moves {
+        clock.moves = moves;
+    }
+}
+
+fn tick_clock(
+    time: Res<Time>,
+    game: Res<Game>,
+    game_state: Res<GameState>,
+    mut clock: ResMut<Clock>,
+) {
+    if game_state.is_over() {
+        return;
+    }
+
+    clock.tick(game.side_to_move(), time.delta());
+}
+
+/// Ends the game once a player runs out of time. The opponent wins, unless
+/// they could not have mated by any series of moves.
+fn flag_fall(game: Res<Game>, clock: Res<Clock>, mut game_state: ResMut<GameState>) {
+    let Some(flagged) = [Color::White, Color::Black]
+        .into_iter()
+        .find(|&color| clock.is_flagged(color))
+    else {
+        return;
+    };
+
+    let new_state = match (game.can_checkmate(flagged.opposite()), flagged) {
+        (false, _) => GameState::Draw(DrawReason::TimeoutWithInsufficientMaterial),
+        (true, Color::White) => GameState::BlackWins,
+        (true, Color::Black) => GameState::WhiteWins,
+    };
+
+    // The state is checked again after each move, e.g. one the engine was
+    // still thinking about when the flag fell
+    if *game_state != new_state {
+        info!("{:?} ran out of time, {:?}", flagged, new_state);
+        *game_state = new_state;
+    }
+}
+
+/// Keeps the panel to the right of the board and each clock on the side of
+/// its player, whichever way the board is shown.
+fn layout_clocks(
+    orientation: Res<Orientation>,
+    mut camera_query: Query<&mut Transform, (With<Camera>, Without<ClockText>)>,
+    mut text_query: Query<(&ClockText, &mut Transform), Without<Camera>>,
+) {
+    if !orientation.is_changed() {
+        return;
+    }
+
+    // The camera turns around its own position when flipped, so the center
+    // is shifted towards where the panel ends up
+    let (camera_x, panel_x) = match *orientation {
+        Orientation::White => (TILE_SIZE * 4.0 + PANEL_WIDTH / 2.0, TILE_SIZE * 8.0),
+        Orientation::Black => (TILE_SIZE * 4.0 - PANEL_WIDTH / 2.0, -PANEL_WIDTH),
+    };
+
+    for mut transform in &mut camera_query {
+        transform.translation.x = camera_x;
+    }
+
+    for (ClockText(color), mut transform) in &mut text_query {
+        let y = match color {
+            Color::White => TILE_SIZE * 0.5,
+            Color::Black => TILE_SIZE * 7.5,
+        };
+        transform.translation = Vec3::new(panel_x + PANEL_WIDTH / 2.0, y, 1.0);
+    }
+}
+
+fn update_clock_text(
+    game: Res<Game>,
+    game_state: Res<GameState>,
+    clock: Res<Clock>,
+    mut text_query: Query<(&ClockText, &mut Text)>,
+) {
+    for (ClockText(color), mut text) in &mut text_query {
+        let section = &mut text.sections[0];
+        section.value = format_clock(clock.remaining(*color));
+        section.style.color = match clock.is_flagged(*color) {
+            true => FLAGGED_COLOR,
+            false if !game_state.is_over() && game.side_to_move() == *color => RUNNING_COLOR,
+            false => IDLE_COLOR,
+        };
+    }
+}
+
+/// Formats the time left as `m:ss`, with tenths of a second in the last ten
+/// seconds.
+fn format_clock(remaining: Duration) -> String {
+    let tenths = remaining.as_millis() / 100;
+    let seconds = tenths / 10;
+
+    match seconds < 10 {
+        true => format!("0:{:02}.{}", seconds, tenths % 10),
+        false => format!("{}:{:02}", seconds / 60, seconds % 60),
+    }
+}
@@ -0,0 +1,164 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+
+use bevy::{
+    prelude::*,
+    tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task},
+};
+use chess_core::{
+    search,
+    uci::{StopHandle, UciEngine, UciError},
+    Move, SearchLimits,
+};
+
+use crate::{
+    game::{Game, GameMode, GameState},
+    replay::Replay,
+};
+
+pub struct EnginePlugin;
+
+impl Plugin for EnginePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EngineLimits>()
+            .init_resource::<Opponent>()
+            .add_systems(
+                Update,
+                (
+                    start_search.run_if(
+                        not(resource_exists::<Search>).and_then(not(resource_exists::<Replay>)),
+                    ),
+                    finish_search.run_if(resource_exists::<Search>),
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// How long the engine thinks about each move.
+#[derive(Resource, Clone, Copy, Debug, Deref, DerefMut)]
+pub struct EngineLimits(pub SearchLimits);
+
+impl Default for EngineLimits {
+    fn default() -> Self {
+        EngineLimits(SearchLimits::time(std::time::Duration::from_secs(1)))
+    }
+}
+
+/// What plays the moves of the sides not played by a human.
+#[derive(Resource, Clone, Default)]
+pub enum Opponent {
+    /// The alpha-beta search of `chess_core`.
+    #[default]
+    BuiltIn,
+    /// An external engine spoken to over UCI, with a handle to stop its
+    /// searches while the engine is locked by one.
+    Uci(Arc<Mutex<UciEngine>>, StopHandle),
+}
+
+impl Opponent {
+    pub fn uci(engine: UciEngine) -> Opponent {
+        let stop = engine.stop_handle();
+        Opponent::Uci(Arc::new(Mutex::new(engine)), stop)
+    }
+}
+
+/// A search running on the async compute pool, for the position described
+/// by `fen`.
+#[derive(Resource)]
+struct Search {
+    task: Task<Result<Option<Move>, UciError>>,
+    fen: String,
+    stop: Arc<AtomicBool>,
+    /// Stops the search of an external engine.
+    uci_stop: Option<StopHandle>,
+}
+
+fn start_search(
+    mut commands: Commands,
+    game: Res<Game>,
+    game_state: Res<GameState>,
+    game_mode: Res<GameMode>,
+    limits: Res<EngineLimits>,
+    opponent: Res<Opponent>,
+) {
+    if game_state.is_over() || !game_mode.is_engine(game.side_to_move()) {
+        return;
+    }
+
+    let position = game.0.clone();
+    let limits = limits.0;
+    let opponent = opponent.clone();
+    let uci_stop = match &opponent {
+        Opponent::BuiltIn => None,
+        Opponent::Uci(_, stop) => Some(stop.clone()),
+    };
+    let stop = Arc::new(AtomicBool::new(false));
+    let task_stop = stop.clone();
+
+    let task = AsyncComputeTaskPool::get().spawn(async move {
+        match opponent {
+            Opponent::BuiltIn => {
+                let result = search(&position, limits, &task_stop, |_| {});
+                debug!(
+                    "Searched depth {} in {:?}, score {}",
+                    result.depth, result.elapsed, result.score
+                );
+                Ok(result.best_move)
+            }
+            Opponent::Uci(engine, _) => {
+                let mut engine = engine.lock().expect("engine lock poisoned");
+                let result = engine.go(&position, limits, |info| debug!("{}", info))?;
+                Ok(result.best_move)
+            }
+        }
+    });
+
+    commands.insert_resource(Search {
+        task,
+        fen: game.to_fen(),
+        stop,
+        uci_stop,
+    });
+}
+
+fn finish_search(
+    mut commands: Commands,
+    mut search: ResMut<Search>,
+    mut game: ResMut<Game>,
+    game_state: Res<GameState>,
+    mut opponent: ResMut<Opponent>,
+) {
+    // The position changed while thinking, e.g. by an undo, or the game
+    // ended by a flag fall or a claimed draw
+    if game.to_fen() != search.fen || game_state.is_over() {
+        search.stop.store(true, Ordering::Relaxed);
+        if let Some(Err(err)) = search.uci_stop.as_ref().map(StopHandle::stop) {
+            warn!("Cannot stop the external engine: {}", err);
+        }
+        commands.remove_resource::<Search>();
+        return;
+    }
+
+    let Some(result) = block_on(future::poll_once(&mut search.task)) else {
+        return;
+    };
+    commands.remove_resource::<Search>();
+
+    match result {
+        Err(err) => {
+            warn!(
+                "External engine failed, playing on with the built-in one: {}",
+                err
+            );
+            *opponent = Opponent::BuiltIn;
+        }
+        Ok(Some(mv)) if game.is_legal(mv) => {
+            info!("Engine plays {}", game.to_san(mv));
+            game.make_move(mv);
+        }
+        _ => warn!("Engine found no move to play"),
+    }
+}
@@ -0,0 +1,217 @@
+use std::{
+    fs,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::prelude::*;
+use chess_core::{Color, DrawReason, GameResult, Outcome, Pgn, Position};
+
+pub struct GamePlugin;
+
+impl Plugin for GamePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Game>()
+            .init_resource::<GameMode>()
+            .init_resource::<GameState>()
+            .add_event::<LoadFen>()
+            .add_systems(Update, (load_fen, export_fen, save_pgn, claim_draw))
+            .add_systems(
+                PostUpdate,
+                update_game_state.run_if(resource_changed::<Game>),
+            );
+    }
+}
+
+/// The position being played, which piece sprites are synced from.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct Game(pub Position);
+
+/// Replaces the game with the position described by a FEN string.
+#[derive(Event, Clone, Debug)]
+pub struct LoadFen(pub String);
+
+/// Which sides are played by a human and which by the engine.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GameMode {
+    #[default]
+    HumanVsHuman,
+    HumanVsEngine {
+        human: Color,
+    },
+    EngineVsEngine,
+    /// The other side is played on another machine.
+    HumanVsRemote {
+        human: Color,
+    },
+}
+
+impl GameMode {
+    /// Whether the side is played with the mouse on this machine.
+    pub fn is_human(&self, color: Color) -> bool {
+        match self {
+            GameMode::HumanVsHuman => true,
+            GameMode::HumanVsEngine { human } | GameMode::HumanVsRemote { human } => {
+                *human == color
+            }
+            GameMode::EngineVsEngine => false,
+        }
+    }
+
+    pub fn is_engine(&self, color: Color) -> bool {
+        match self {
+            GameMode::HumanVsHuman | GameMode::HumanVsRemote { .. } => false,
+            GameMode::HumanVsEngine { human } => *human != color,
+            GameMode::EngineVsEngine => true,
+        }
+    }
+}
+
+#[derive(Resource, Clone, Debug, PartialEq, Eq)]
+pub enum GameState {
+    InProgress { check: bool },
+    WhiteWins,
+    BlackWins,
+    Draw(DrawReason),
+}
+
+impl Default for GameState {
+    fn default() -> Self {
+        GameState::InProgress { check: false }
+    }
+}
+
+impl GameState {
+    pub fn is_over(&self) -> bool {
+        !matches!(self, GameState::InProgress { .. })
+    }
+
+    pub fn result(&self) -> GameResult {
+        match self {
+            GameState::InProgress { .. } => GameResult::Ongoing,
+            GameState::WhiteWins => GameResult::WhiteWins,
+            GameState::BlackWins => GameResult::BlackWins,
+            GameState::Draw(_) => GameResult::Draw,
+        }
+    }
+}
+
+/// Evaluates the position for the side whose turn it is.
+pub fn get_game_state(position: &Position) -> GameState {
+    match position.outcome() {
+        None => GameState::InProgress {
+            check: position.is_check(),
+        },
+        Some(Outcome::Checkmate {
+            winner: Color::White,
+        }) => GameState::WhiteWins,
+        Some(Outcome::Checkmate {
+            winner: Color::Black,
+        }) => GameState::BlackWins,
+        Some(Outcome::Draw(reason)) => GameState::Draw(reason),
+    }
+}
+
+pub fn update_game_state(game: Res<Game>, mut game_state: ResMut<GameState>) {
+    let new_state = get_game_state(&game);
+
+    match &new_state {
+        GameState::InProgress { check: true } => info!("{:?} is in check", game.side_to_move()),
+        GameState::WhiteWins => info!("Checkmate, white wins"),
+        GameState::BlackWins => info!("Checkmate, black wins"),
+        GameState::Draw(reason) => info!("Draw by {:?}", reason),
+        GameState::InProgress { check: false } => {}
+    }
+
+    if let (false, Some(reason)) = (new_state.is_over(), game.claimable_draw()) {
+        info!("A draw by {:?} can be claimed with D", reason);
+    }
+
+    *game_state = new_state;
+}
+
+/// Ends the game in a draw when pressing D, if the fifty-move rule or a
+/// threefold repetition allows claiming one.
+fn claim_draw(keys: Res<ButtonInput<KeyCode>>, game: Res<Game>, mut game_state: ResMut<GameState>) {
+    if !keys.just_pressed(KeyCode::KeyD) || game_state.is_over() {
+        return;
+    }
+
+    match game.claimable_draw() {
+        Some(reason) => {
+            info!("Draw by {:?} claimed", reason);
+            *game_state = GameState::Draw(reason);
+        }
+        None => info!("No draw can be claimed"),
+    }
+}
+
+fn load_fen(mut load_fen_reader: EventReader<LoadFen>, mut game: ResMut<Game>) {
+    for LoadFen(fen) in load_fen_reader.read() {
+        match Position::from_fen(fen) {
+            Ok(position) => game.0 = position,
+            Err(err) => warn!("Cannot load FEN `{}`: {}", fen, err),
+        }
+    }
+}
+
+/// Logs the current position as FEN when pressing F.
+fn export_fen(keys: Res<ButtonInput<KeyCode>>, game: Res<Game>) {
+    if keys.just_pressed(KeyCode::KeyF) {
+        info!("{}", game.to_fen());
+    }
+}
+
+/// Saves the game played so far as a PGN file when pressing S.
+fn save_pgn(
+    keys: Res<ButtonInput<KeyCode>>,
+    game: Res<Game>,
+    game_state: Res<GameState>,
+    game_mode: Res<GameMode>,
+) {
+    if !keys.just_pressed(KeyCode::KeyS) {
+        return;
+    }
+
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+
+    let player = |color| match (game_mode.is_human(color), game_mode.is_engine(color)) {
+        (true, _) => "Human",
+        (false, true) => "Engine",
+        (false, false) => "Remote",
+    };
+
+    let mut pgn = Pgn::from_position(&game, game_state.result());
+    pgn.set_tag("Event", "Casual game");
+    pgn.set_tag("Site", "bevy-chess");
+    pgn.set_tag("Date", &format_date(seconds));
+    pgn.set_tag("White", player(Color::White));
+    pgn.set_tag("Black", player(Color::Black));
+
+    let path = format!("game-{seconds}.pgn");
+    match fs::write(&path, pgn.to_string()) {
+        Ok(()) => info!("Saved game to {}", path),
+        Err(err) => warn!("Cannot save game to {}: {}", path, err),
+    }
+}
+
+/// Formats a UTC date as PGN `YYYY.MM.DD` from seconds since the Unix epoch.
+fn format_date(seconds: u64) -> String {
+    // Civil from days algorithm, see http://howardhinnant.github.io/date_algorithms.html
+    let days = (seconds / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = match shifted_month < 10 {
+        true => shifted_month + 3,
+        false => shifted_month - 9,
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{year:04}.{month:02}.{day:02}")
+}
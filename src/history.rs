@@ -0,0 +1,125 @@
+use bevy::prelude::*;
+use chess_core::Move;
+
+use crate::{
+    game::{Game, GameMode},
+    network::Remote,
+    promotion::promotion_pending,
+    replay::Replay,
+};
+
+pub struct HistoryPlugin;
+
+impl Plugin for HistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RedoStack>()
+            .add_event::<Undo>()
+            .add_event::<Redo>()
+            .add_systems(
+                Update,
+                (history_keys, undo_move, redo_move).chain().run_if(
+                    not(promotion_pending)
+                        .and_then(not(resource_exists::<Replay>))
+                        .and_then(not(resource_exists::<Remote>)),
+                ),
+            )
+            .add_systems(PostUpdate, clear_redo.run_if(resource_changed::<Game>));
+    }
+}
+
+/// Takes back the last move of the game.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct Undo;
+
+/// Plays again the last move taken back.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct Redo;
+
+/// Moves taken back, most recent last, along with the moves left in the game
+/// when they were. Playing any other move forgets them.
+#[derive(Resource, Default, Debug)]
+pub struct RedoStack {
+    moves: Vec<Move>,
+    history: Vec<Move>,
+}
+
+fn history_keys(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut undo_writer: EventWriter<Undo>,
+    mut redo_writer: EventWriter<Redo>,
+) {
+    let ctrl = keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    if !ctrl {
+        return;
+    }
+
+    if keys.just_pressed(KeyCode::KeyZ) && !shift {
+        undo_writer.send(Undo);
+    }
+    if keys.just_pressed(KeyCode::KeyY) || (keys.just_pressed(KeyCode::KeyZ) && shift) {
+        redo_writer.send(Redo);
+    }
+}
+
+fn undo_move(
+    mut undo_reader: EventReader<Undo>,
+    mut game: ResMut<Game>,
+    mut redo_stack: ResMut<RedoStack>,
+    game_mode: Res<GameMode>,
+) {
+    for _ in undo_reader.read() {
+        let Some(mv) = game.unmake_move() else {
+            info!("No move to undo");
+            continue;
+        };
+        redo_stack.moves.push(mv);
+
+        // Against the engine, its reply is taken back along with the move
+        while engine_to_move(&game, &game_mode) {
+            match game.unmake_move() {
+                Some(mv) => redo_stack.moves.push(mv),
+                None => break,
+            }
+        }
+        redo_stack.history = game.moves().collect();
+    }
+}
+
+fn redo_move(
+    mut redo_reader: EventReader<Redo>,
+    mut game: ResMut<Game>,
+    mut redo_stack: ResMut<RedoStack>,
+    game_mode: Res<GameMode>,
+) {
+    for _ in redo_reader.read() {
+        if redo_stack.moves.is_empty() {
+            info!("No move to redo");
+            continue;
+        }
+
+        // Against the engine, its reply is played again along with the move
+        loop {
+            match redo_stack.moves.pop() {
+                Some(mv) if game.is_legal(mv) => game.make_move(mv),
+                Some(_) => redo_stack.moves.clear(),
+                None => break,
+            }
+            if !engine_to_move(&game, &game_mode) {
+                break;
+            }
+        }
+        redo_stack.history = game.moves().collect();
+    }
+}
+
+fn engine_to_move(game: &Game, game_mode: &GameMode) -> bool {
+    matches!(game_mode, GameMode::HumanVsEngine { .. }) && !game_mode.is_human(game.side_to_move())
+}
+
+/// Forgets the moves taken back once the game goes another way.
+fn clear_redo(game: Res<Game>, mut redo_stack: ResMut<RedoStack>) {
+    if !redo_stack.moves.is_empty() && !game.moves().eq(redo_stack.history.iter().copied()) {
+        redo_stack.moves.clear();
+    }
+}
@@ -1,38 +1,159 @@
-use bevy::{prelude::*, window::PrimaryWindow};
+use bevy::{
+    prelude::*,
+    sprite::{MaterialMesh2dBundle, Mesh2dHandle},
+};
+use chess_core::{Move, PieceType, Square};
 
 use crate::{
-    board::{get_pixels_by_pos, get_pos_from_pixel, TILE_SIZE},
-    pieces::{Color, Piece},
+    board::{get_pixels_by_square, BoardCursor, Tile, TILE_SIZE},
+    game::{Game, GameMode, GameState},
+    pieces::Piece,
+    promotion::{promotion_pending, Promoting},
+    replay::Replay,
 };
 
 pub struct InputPlugin;
 
 impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(Color::White).add_systems(
-            Update,
-            (select_piece, move_piece, release_piece, set_piece_position),
-        );
+        app.init_resource::<InputMode>()
+            .add_event::<PlayMove>()
+            .add_systems(Startup, setup_target_markers)
+            .add_systems(
+                Update,
+                (
+                    toggle_input_mode,
+                    select_piece
+                        .run_if(can_select.and_then(resource_equals(InputMode::DragAndDrop))),
+                    click_piece
+                        .run_if(can_select.and_then(resource_equals(InputMode::ClickToMove))),
+                    move_piece.run_if(resource_equals(InputMode::DragAndDrop)),
+                    release_piece.run_if(resource_equals(InputMode::DragAndDrop)),
+                    play_move,
+                    set_piece_position,
+                    (show_move_targets, hide_move_targets),
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// How pieces are moved with the mouse, switched with M.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InputMode {
+    /// Press on a piece, drag it and release it on the target square.
+    #[default]
+    DragAndDrop,
+    /// Click on a piece, then on the target square.
+    ClickToMove,
+}
+
+/// A move made by the human player, played once checked to be legal.
+/// Without a promotion given, a pawn reaching the last rank waits for one to
+/// be chosen.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PlayMove {
+    pub from: Square,
+    pub to: Square,
+    pub promotion: Option<PieceType>,
+}
+
+impl PlayMove {
+    pub fn new(from: Square, to: Square) -> PlayMove {
+        PlayMove {
+            from,
+            to,
+            promotion: None,
+        }
+    }
+}
+
+impl From<Move> for PlayMove {
+    fn from(mv: Move) -> Self {
+        PlayMove {
+            from: mv.from,
+            to: mv.to,
+            promotion: mv.promotion,
+        }
     }
 }
 
+/// A piece picked up to be moved, with its legal moves.
+#[derive(Component)]
+pub struct Selected(Vec<Move>);
+
+/// Marks a square the selected piece can move to, as a child of its `Tile`.
 #[derive(Component)]
-struct Selected(Vec<IVec2>);
+struct TargetMarker;
+
+/// Shapes drawn on target squares: a dot for quiet moves and a ring around
+/// the piece for captures.
+#[derive(Resource)]
+struct TargetMarkers {
+    dot: Mesh2dHandle,
+    ring: Mesh2dHandle,
+    material: Handle<ColorMaterial>,
+}
+
+fn setup_target_markers(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.insert_resource(TargetMarkers {
+        dot: Mesh2dHandle(meshes.add(Circle::new(TILE_SIZE * 0.15))),
+        ring: Mesh2dHandle(meshes.add(Annulus::new(TILE_SIZE * 0.4, TILE_SIZE * 0.5))),
+        material: materials.add(Color::srgba(0.0, 0.0, 0.0, 0.2)),
+    });
+}
+
+/// Whether a human may pick up a piece to move.
+pub fn can_select(
+    game: Res<Game>,
+    game_state: Res<GameState>,
+    game_mode: Res<GameMode>,
+    promoting_query: Query<(), With<Promoting>>,
+    replay: Option<Res<Replay>>,
+) -> bool {
+    !game_state.is_over()
+        && game_mode.is_human(game.side_to_move())
+        && !promotion_pending(promoting_query)
+        && replay.is_none()
+}
+
+fn toggle_input_mode(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut input_mode: ResMut<InputMode>,
+    selected_query: Query<Entity, With<Selected>>,
+) {
+    if !keys.just_pressed(KeyCode::KeyM) {
+        return;
+    }
+
+    *input_mode = match *input_mode {
+        InputMode::DragAndDrop => InputMode::ClickToMove,
+        InputMode::ClickToMove => InputMode::DragAndDrop,
+    };
+    info!("Moving pieces by {:?}", *input_mode);
+
+    for entity in &selected_query {
+        commands.entity(entity).remove::<Selected>();
+    }
+}
 
 fn select_piece(
     mut commands: Commands,
     buttons: Res<ButtonInput<MouseButton>>,
-    color_turn: Res<Color>,
+    game: Res<Game>,
     pieces_query: Query<(Entity, &Piece)>,
-    windows_query: Query<&Window, With<PrimaryWindow>>,
+    cursor: BoardCursor,
 ) {
     if buttons.just_pressed(MouseButton::Left) {
-        if let Some(mouse_pos) = windows_query.single().cursor_position() {
-            let new_position = get_pos_from_pixel(mouse_pos);
+        if let Some(new_square) = cursor.square() {
             for (entity, piece) in &pieces_query {
-                if piece.position == new_position && piece.color == *color_turn {
-                    let valid_moves =
-                        piece.get_valid_moves(pieces_query.iter().map(|(_, p)| p).collect());
+                if piece.square == new_square && piece.color == game.side_to_move() {
+                    let valid_moves = game.legal_moves_from(piece.square);
 
                     commands.entity(entity).insert(Selected(valid_moves));
                 }
@@ -41,14 +162,73 @@ fn select_piece(
     }
 }
 
+/// Selects a piece on a first click and moves it on a second one.
+fn click_piece(
+    mut commands: Commands,
+    buttons: Res<ButtonInput<MouseButton>>,
+    game: Res<Game>,
+    pieces_query: Query<(Entity, &Piece, Has<Selected>)>,
+    mut play_move: EventWriter<PlayMove>,
+    cursor: BoardCursor,
+) {
+    if !buttons.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    if let Some(square) = cursor.square() {
+        select_or_move(&mut commands, &game, &pieces_query, &mut play_move, square);
+    }
+}
+
+/// Selects the piece of the side to move on `square`, or moves the selected
+/// piece there. Choosing another piece of the same side selects it instead,
+/// and choosing the selected piece again deselects it.
+pub fn select_or_move(
+    commands: &mut Commands,
+    game: &Game,
+    pieces_query: &Query<(Entity, &Piece, Has<Selected>)>,
+    play_move: &mut EventWriter<PlayMove>,
+    square: Square,
+) {
+    let chosen = pieces_query
+        .iter()
+        .find(|(_, piece, _)| piece.square == square && piece.color == game.side_to_move());
+    let selected = pieces_query.iter().find(|(_, _, selected)| *selected);
+
+    if let Some((entity, piece, _)) = selected {
+        commands.entity(entity).remove::<Selected>();
+
+        match chosen {
+            None => {
+                play_move.send(PlayMove::new(piece.square, square));
+                return;
+            }
+            Some((chosen, _, _)) if chosen == entity => return,
+            Some(_) => {}
+        }
+    }
+
+    if let Some((entity, piece, _)) = chosen {
+        commands
+            .entity(entity)
+            .insert(Selected(game.legal_moves_from(piece.square)));
+    }
+}
+
+/// Drags the selected piece along while the mouse button is held.
 fn move_piece(
+    buttons: Res<ButtonInput<MouseButton>>,
     mut piece_query: Query<&mut Transform, With<Selected>>,
-    mut cursore_moved_reader: EventReader<CursorMoved>,
+    cursor: BoardCursor,
 ) {
+    if !buttons.pressed(MouseButton::Left) {
+        return;
+    }
+
     if let Ok(mut transform) = piece_query.get_single_mut() {
-        for cursor_moved in cursore_moved_reader.read() {
-            transform.translation.x = cursor_moved.position.x;
-            transform.translation.y = (TILE_SIZE * 8.0) - cursor_moved.position.y;
+        if let Some(cursor_pos) = cursor.world_position() {
+            transform.translation.x = cursor_pos.x;
+            transform.translation.y = cursor_pos.y;
             transform.translation.z = 2.0;
         }
     }
@@ -57,34 +237,52 @@ fn move_piece(
 fn release_piece(
     mut commands: Commands,
     buttons: Res<ButtonInput<MouseButton>>,
-    mut color_turn: ResMut<Color>,
-    mut selected_piece_query: Query<(Entity, &mut Piece, &Selected)>,
-    pieces_query: Query<(Entity, &Piece), Without<Selected>>,
-    windows_query: Query<&Window, With<PrimaryWindow>>,
+    selected_piece_query: Query<(Entity, &Piece), With<Selected>>,
+    mut play_move: EventWriter<PlayMove>,
+    cursor: BoardCursor,
 ) {
     if buttons.just_released(MouseButton::Left) {
-        if let Ok((entity, mut piece, selected)) = selected_piece_query.get_single_mut() {
-            if let Some(mouse_pos) = windows_query.single().cursor_position() {
-                let target_pos = get_pos_from_pixel(mouse_pos);
-
-                // Check if move is one from the valid ones
-                let move_is_valid = selected.0.iter().any(|valid_pos| valid_pos == &target_pos);
-
-                if move_is_valid {
-                    piece.position = target_pos;
-
-                    // Despawn piece if there is any in a valid spot
-                    if let Some((target_entity, _)) = pieces_query
-                        .iter()
-                        .find(|(_, piece)| piece.position == target_pos)
-                    {
-                        commands.entity(target_entity).despawn();
-                    }
-
-                    // Swap whose turn it is
-                    (*color_turn).switch();
+        if let Ok((entity, piece)) = selected_piece_query.get_single() {
+            if let Some(target) = cursor.square() {
+                play_move.send(PlayMove::new(piece.square, target));
+            }
+            commands.entity(entity).remove::<Selected>();
+        }
+    }
+}
+
+/// Plays the moves made on the board by drag and drop or by clicking.
+fn play_move(
+    mut commands: Commands,
+    mut play_move_reader: EventReader<PlayMove>,
+    mut game: ResMut<Game>,
+    pieces_query: Query<(Entity, &Piece)>,
+) {
+    for &PlayMove {
+        from,
+        to,
+        promotion,
+    } in play_move_reader.read()
+    {
+        // The game may have changed since the piece was picked up, e.g. by
+        // an undo
+        let Some(mv) = game
+            .legal_moves_from(from)
+            .into_iter()
+            .find(|mv| mv.to == to && (promotion.is_none() || mv.promotion == promotion))
+        else {
+            continue;
+        };
+
+        match (mv.promotion, promotion) {
+            (None, _) | (Some(_), Some(_)) => game.make_move(mv),
+            // A pawn reaching the last rank waits for a promotion choice
+            (Some(_), None) => {
+                if let Some((entity, _)) =
+                    pieces_query.iter().find(|(_, piece)| piece.square == from)
+                {
+                    commands.entity(entity).insert(Promoting { from, to });
                 }
-                commands.entity(entity).remove::<Selected>();
             }
         }
     }
@@ -96,10 +294,64 @@ fn set_piece_position(
 ) {
     for entity in removed.read() {
         if let Ok((piece, mut transform)) = query.get_mut(entity) {
-            let pos = get_pixels_by_pos(piece.position);
+            let pos = get_pixels_by_square(piece.square);
             transform.translation.x = pos.x;
             transform.translation.y = pos.y;
             transform.translation.z = 1.0;
         }
     }
 }
+
+fn show_move_targets(
+    mut commands: Commands,
+    game: Res<Game>,
+    markers: Res<TargetMarkers>,
+    selected_query: Query<&Selected, Added<Selected>>,
+    tiles_query: Query<(Entity, &Tile)>,
+) {
+    let Ok(selected) = selected_query.get_single() else {
+        return;
+    };
+
+    for (entity, tile) in &tiles_query {
+        // Promotions have one move per piece type to the same square
+        let Some(mv) = selected.0.iter().find(|mv| mv.to == tile.square) else {
+            continue;
+        };
+
+        let is_en_passant = game
+            .piece_at(mv.from)
+            .is_some_and(|piece| piece.piece_type == PieceType::Pawn)
+            && mv.from.file() != mv.to.file();
+        let mesh = match game.piece_at(mv.to).is_some() || is_en_passant {
+            true => markers.ring.clone(),
+            false => markers.dot.clone(),
+        };
+
+        commands.entity(entity).with_children(|parent| {
+            parent.spawn((
+                TargetMarker,
+                MaterialMesh2dBundle {
+                    mesh,
+                    material: markers.material.clone(),
+                    transform: Transform::from_xyz(0.0, 0.0, 0.5),
+                    ..default()
+                },
+            ));
+        });
+    }
+}
+
+fn hide_move_targets(
+    mut commands: Commands,
+    mut removed: RemovedComponents<Selected>,
+    markers_query: Query<Entity, With<TargetMarker>>,
+) {
+    if removed.read().count() == 0 {
+        return;
+    }
+
+    for entity in &markers_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,328 @@
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState, InputSystem,
+    },
+    prelude::*,
+};
+use chess_core::{Move, Square};
+
+use crate::{
+    board::{get_pixels_by_square, Orientation, Upright, TILE_SIZE},
+    game::{Game, LoadFen},
+    input::{can_select, select_or_move, PlayMove, Selected},
+    network::Remote,
+    pieces::Piece,
+    replay::Replay,
+};
+
+pub struct KeyboardPlugin;
+
+impl Plugin for KeyboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<KeyboardCursor>()
+            .add_event::<TypedMove>()
+            .add_systems(Startup, setup_keyboard_cursor)
+            .add_systems(
+                PreUpdate,
+                edit_move_box
+                    .after(InputSystem)
+                    .run_if(resource_exists::<MoveBox>),
+            )
+            .add_systems(
+                Update,
+                (
+                    open_move_box.run_if(not(resource_exists::<MoveBox>)),
+                    play_typed_move.run_if(can_select),
+                    load_typed_fen.run_if(
+                        not(resource_exists::<Replay>).and_then(not(resource_exists::<Remote>)),
+                    ),
+                    move_keyboard_cursor.run_if(not(resource_exists::<Replay>)),
+                    pick_up_with_keyboard.run_if(can_select),
+                    cancel_selection,
+                    show_keyboard_cursor,
+                    show_move_box,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Square the arrow keys move over, hidden until they are first used.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyboardCursor {
+    pub square: Square,
+    pub visible: bool,
+}
+
+impl Default for KeyboardCursor {
+    fn default() -> Self {
+        KeyboardCursor {
+            square: Square::new(4, 1),
+            visible: false,
+        }
+    }
+}
+
+/// A move being typed in SAN or coordinate notation, or a FEN position to
+/// load, opened with Tab. While it is open, keys go to it instead of the
+/// board and other shortcuts.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct MoveBox(pub String);
+
+/// Text submitted from the move box: a move, played once parsed and checked
+/// to be legal, or a FEN position, loaded in place of the game.
+#[derive(Event, Clone, Debug)]
+pub struct TypedMove(pub String);
+
+const ARROWS: [(KeyCode, (i32, i32)); 4] = [
+    (KeyCode::ArrowLeft, (-1, 0)),
+    (KeyCode::ArrowRight, (1, 0)),
+    (KeyCode::ArrowUp, (0, 1)),
+    (KeyCode::ArrowDown, (0, -1)),
+];
+
+#[derive(Component)]
+struct CursorHighlight;
+
+#[derive(Component)]
+struct MoveBoxText;
+
+fn setup_keyboard_cursor(mut commands: Commands) {
+    commands.spawn((
+        CursorHighlight,
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::srgba(0.2, 0.4, 0.9, 0.4),
+                custom_size: Some(Vec2::splat(TILE_SIZE)),
+                ..default()
+            },
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+    ));
+}
+
+fn move_keyboard_cursor(
+    keys: Res<ButtonInput<KeyCode>>,
+    orientation: Res<Orientation>,
+    mut cursor: ResMut<KeyboardCursor>,
+) {
+    if !keys.any_just_pressed(ARROWS.map(|(key, _)| key)) {
+        return;
+    }
+
+    // The arrows follow the board as shown, upside down from black's side
+    let direction = match *orientation {
+        Orientation::White => 1,
+        Orientation::Black => -1,
+    };
+
+    for (key, (files, ranks)) in ARROWS {
+        if !keys.just_pressed(key) {
+            continue;
+        }
+        if let Some(square) = cursor.square.offset(files * direction, ranks * direction) {
+            cursor.square = square;
+        }
+    }
+    cursor.visible = true;
+}
+
+/// Picks up the piece under the keyboard cursor with Enter, or drops the
+/// selected one there.
+fn pick_up_with_keyboard(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    game: Res<Game>,
+    mut cursor: ResMut<KeyboardCursor>,
+    pieces_query: Query<(Entity, &Piece, Has<Selected>)>,
+    mut play_move: EventWriter<PlayMove>,
+) {
+    if !keys.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter]) {
+        return;
+    }
+
+    cursor.visible = true;
+    select_or_move(
+        &mut commands,
+        &game,
+        &pieces_query,
+        &mut play_move,
+        cursor.square,
+    );
+}
+
+/// Puts down the selected piece without moving it when pressing Escape.
+fn cancel_selection(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    selected_query: Query<Entity, With<Selected>>,
+) {
+    if !keys.just_pressed(KeyCode::Escape) {
+        return;
+    }
+
+    for entity in &selected_query {
+        commands.entity(entity).remove::<Selected>();
+    }
+}
+
+fn show_keyboard_cursor(
+    cursor: Res<KeyboardCursor>,
+    mut highlight_query: Query<(&mut Transform, &mut Visibility), With<CursorHighlight>>,
+) {
+    if !cursor.is_changed() {
+        return;
+    }
+
+    for (mut transform, mut visibility) in &mut highlight_query {
+        let position = get_pixels_by_square(cursor.square);
+        transform.translation = position.extend(0.4);
+        *visibility = match cursor.visible {
+            true => Visibility::Visible,
+            false => Visibility::Hidden,
+        };
+    }
+}
+
+fn open_move_box(mut commands: Commands, keys: Res<ButtonInput<KeyCode>>) {
+    if keys.just_pressed(KeyCode::Tab) {
+        commands.init_resource::<MoveBox>();
+    }
+}
+
+/// Types into the move box, submitting it with Enter and closing it with
+/// Escape. Runs before other systems see the keys, which are then cleared
+/// so typing does not trigger shortcuts.
+fn edit_move_box(
+    mut commands: Commands,
+    mut keyboard_reader: EventReader<KeyboardInput>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    mut move_box: ResMut<MoveBox>,
+    mut typed_move: EventWriter<TypedMove>,
+) {
+    for event in keyboard_reader.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Character(text) => {
+                move_box
+                    .0
+                    .extend(text.chars().filter(|c| c.is_ascii_graphic()));
+            }
+            Key::Space => move_box.0.push(' '),
+            Key::Backspace => {
+                move_box.0.pop();
+            }
+            Key::Enter => {
+                typed_move.send(TypedMove(move_box.0.trim().to_string()));
+            }
+            Key::Escape => commands.remove_resource::<MoveBox>(),
+            _ => {}
+        }
+    }
+
+    keys.clear();
+}
+
+/// Plays a move typed in SAN, e.g. `Nf3` or `exd8=Q`, or in coordinate
+/// notation, e.g. `g1f3` or `e7d8q`.
+fn play_typed_move(
+    mut commands: Commands,
+    mut typed_move_reader: EventReader<TypedMove>,
+    game: Res<Game>,
+    mut play_move: EventWriter<PlayMove>,
+) {
+    for TypedMove(text) in typed_move_reader.read() {
+        if is_fen(text) {
+            continue;
+        }
+
+        let mv = match text.parse::<Move>() {
+            Ok(mv) if game.is_legal(mv) => Ok(mv),
+            _ => game.parse_san(text).map_err(|err| err.to_string()),
+        };
+
+        match mv {
+            Ok(mv) => {
+                play_move.send(PlayMove::from(mv));
+                commands.remove_resource::<MoveBox>();
+            }
+            Err(err) => warn!("Cannot play `{}`: {}", text, err),
+        }
+    }
+}
+
+/// Loads a position typed as FEN, which unlike moves contains `/` between
+/// the ranks. Not in network games, where the peer would not follow.
+fn load_typed_fen(
+    mut commands: Commands,
+    mut typed_move_reader: EventReader<TypedMove>,
+    mut load_fen: EventWriter<LoadFen>,
+) {
+    for TypedMove(text) in typed_move_reader.read() {
+        if is_fen(text) {
+            load_fen.send(LoadFen(text.clone()));
+            commands.remove_resource::<MoveBox>();
+        }
+    }
+}
+
+fn is_fen(text: &str) -> bool {
+    text.contains('/')
+}
+
+fn show_move_box(
+    mut commands: Commands,
+    move_box: Option<Res<MoveBox>>,
+    mut text_query: Query<(Entity, &mut Text), With<MoveBoxText>>,
+) {
+    let Some(move_box) = move_box else {
+        for (entity, _) in &text_query {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    };
+
+    let value = format!("Move: {}_", move_box.0);
+    if let Ok((_, mut text)) = text_query.get_single_mut() {
+        if move_box.is_changed() {
+            text.sections[0].value = value;
+        }
+        return;
+    }
+
+    // Shown across the middle of the board, which stays in place when the
+    // board is flipped
+    commands
+        .spawn((
+            MoveBoxText,
+            Upright,
+            Text2dBundle {
+                text: Text::from_section(
+                    value,
+                    TextStyle {
+                        font_size: 40.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ),
+                transform: Transform::from_xyz(TILE_SIZE * 4.0, TILE_SIZE * 4.0, 5.0),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: Color::srgba(0.0, 0.0, 0.0, 0.7),
+                    custom_size: Some(Vec2::new(TILE_SIZE * 6.0, TILE_SIZE * 0.8)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, 0.0, -0.1),
+                ..default()
+            });
+        });
+}
@@ -1,30 +1,201 @@
 mod board;
+mod cli;
+mod clock;
+mod engine;
+mod game;
+mod history;
 mod input;
+mod keyboard;
+mod network;
 mod pieces;
+mod promotion;
+mod replay;
 
-use bevy::{prelude::*, window::WindowResolution};
-use board::{setup_board, TILE_SIZE};
+use std::{
+    net::TcpListener,
+    process::{self, Command},
+};
+
+use bevy::{prelude::*, render::camera::ScalingMode, window::WindowResolution};
+use board::{
+    cycle_orientation_mode, follow_orientation_mode, orient_board, place_coordinates, setup_board,
+    setup_coordinates, Orientation, OrientationMode, TILE_SIZE,
+};
+use chess_core::{
+    net::{NetError, Peer},
+    uci::UciEngine,
+    ChessClock, Color, Pgn, Position,
+};
+use cli::Options;
+use clock::{Clock, ClockPlugin, PANEL_WIDTH};
+use engine::{EnginePlugin, Opponent};
+use game::{Game, GameMode, GamePlugin};
+use history::HistoryPlugin;
 use input::InputPlugin;
-use pieces::spawn_pieces;
+use keyboard::KeyboardPlugin;
+use network::{NetworkPlugin, Remote};
+use pieces::PiecesPlugin;
+use promotion::PromotionPlugin;
+use replay::{Replay, ReplayPlugin};
 
 fn main() {
-    App::new()
-        .add_plugins(DefaultPlugins.set(WindowPlugin {
-            primary_window: Some(Window {
-                resolution: WindowResolution::new(TILE_SIZE * 8.0, TILE_SIZE * 8.0),
-                resizable: false,
-                ..default()
-            }),
+    let options = Options::from_env();
+
+    // The clocks are shown in a panel to the right of the board
+    let board_width = options.tile_size * 8.0;
+    let window_width = match options.time_control {
+        Some(_) => board_width + PANEL_WIDTH * options.tile_size / TILE_SIZE,
+        None => board_width,
+    };
+
+    // Added first so messages are logged while starting the engine or
+    // waiting for an opponent
+    let mut app = App::new();
+    app.add_plugins(DefaultPlugins.set(WindowPlugin {
+        primary_window: Some(Window {
+            resolution: WindowResolution::new(window_width, board_width),
             ..default()
-        }))
-        .add_systems(Startup, (setup_camera, setup_board, spawn_pieces))
-        .add_plugins(InputPlugin)
-        .run();
+        }),
+        ..default()
+    }));
+
+    let replay = options.pgn.as_ref().map(|path| {
+        let pgn = std::fs::read_to_string(path)
+            .map_err(|err| err.to_string())
+            .and_then(|text| text.parse::<Pgn>().map_err(|err| err.to_string()))
+            .unwrap_or_else(|err| {
+                eprintln!("error: cannot load {}: {err}", path.display());
+                process::exit(2);
+            });
+        Replay::new(pgn)
+    });
+
+    let mut position = match (&replay, &options.fen) {
+        (Some(replay), _) => replay.pgn.start_position.clone(),
+        (None, Some(fen)) => Position::from_fen(fen).unwrap_or_else(|err| {
+            eprintln!("error: invalid FEN: {err}");
+            process::exit(2);
+        }),
+        (None, None) => Position::new(),
+    };
+
+    let opponent = match &options.engine {
+        Some(path) => {
+            let engine = UciEngine::spawn(Command::new(path)).unwrap_or_else(|err| {
+                eprintln!("error: cannot start {}: {err}", path.display());
+                process::exit(2);
+            });
+            info!(
+                "Playing against {}",
+                engine.name.as_deref().unwrap_or("an unnamed engine")
+            );
+            Opponent::uci(engine)
+        }
+        None => Opponent::BuiltIn,
+    };
+
+    let mut mode = options.mode;
+    let mut orientation_mode = match (options.auto_flip, options.flip) {
+        (true, _) => OrientationMode::AutoFlip,
+        (false, true) => OrientationMode::Black,
+        (false, false) => OrientationMode::White,
+    };
+
+    // Over the network, the host decides the position and who plays what
+    let remote = connect(&options, &position).map(|peer| {
+        position = peer.start_position.clone();
+        mode = GameMode::HumanVsRemote { human: peer.color };
+        orientation_mode = match peer.color {
+            Color::White => OrientationMode::White,
+            Color::Black => OrientationMode::Black,
+        };
+        Remote::new(peer).unwrap_or_else(|err| {
+            eprintln!("error: {err}");
+            process::exit(2);
+        })
+    });
+
+    let game = Game(position);
+    let clock = options
+        .time_control
+        .map(|time_control| Clock::new(ChessClock::new(time_control), &game));
+
+    app.insert_resource(game)
+        .insert_resource(mode)
+        .insert_resource(options.engine_limits)
+        .insert_resource(opponent)
+        .insert_resource(orientation_mode)
+        .init_resource::<Orientation>()
+        .add_systems(Startup, (setup_camera, setup_board, setup_coordinates))
+        .add_systems(
+            Update,
+            (cycle_orientation_mode, follow_orientation_mode).chain(),
+        )
+        .add_systems(PostUpdate, (orient_board, place_coordinates))
+        .add_plugins((
+            InputPlugin,
+            KeyboardPlugin,
+            GamePlugin,
+            PiecesPlugin,
+            PromotionPlugin,
+            ReplayPlugin,
+            HistoryPlugin,
+            EnginePlugin,
+            NetworkPlugin,
+            ClockPlugin,
+        ));
+
+    if let Some(replay) = replay {
+        app.insert_resource(replay);
+    }
+    if let Some(remote) = remote {
+        app.insert_resource(remote);
+    }
+    if let Some(clock) = clock {
+        app.insert_resource(clock);
+    }
+
+    app.run();
+}
+
+/// Connects to the other player when hosting or joining a network game.
+fn connect(options: &Options, position: &Position) -> Option<Peer> {
+    let peer = match (&options.host, &options.join) {
+        (Some(address), _) => {
+            let color = match options.flip {
+                true => Color::Black,
+                false => Color::White,
+            };
+            TcpListener::bind(address)
+                .map_err(NetError::from)
+                .and_then(|listener| {
+                    info!("Waiting for an opponent on {address}");
+                    Peer::host(&listener, position, color)
+                })
+        }
+        (None, Some(address)) => Peer::join(address.as_str()),
+        (None, None) => return None,
+    };
+
+    Some(peer.unwrap_or_else(|err| {
+        eprintln!("error: cannot connect to the opponent: {err}");
+        process::exit(2);
+    }))
 }
 
-fn setup_camera(mut commands: Commands) {
-    commands.spawn(Camera2dBundle {
-        transform: Transform::from_xyz(400.0, 400.0, 0.0),
+fn setup_camera(mut commands: Commands, clock: Option<Res<Clock>>) {
+    let mut camera = Camera2dBundle {
+        transform: Transform::from_xyz(TILE_SIZE * 4.0, TILE_SIZE * 4.0, 0.0),
         ..Default::default()
-    });
+    };
+
+    // The board is laid out with the default tile size and scaled by the
+    // camera to fit the window, centered along its longer side
+    let panel_width = clock.map_or(0.0, |_| PANEL_WIDTH);
+    camera.projection.scaling_mode = ScalingMode::AutoMin {
+        min_width: TILE_SIZE * 8.0 + panel_width,
+        min_height: TILE_SIZE * 8.0,
+    };
+
+    commands.spawn(camera);
 }
@@ -0,0 +1,128 @@
+use std::{
+    sync::{
+        mpsc::{self, Receiver, TryRecvError},
+        Mutex,
+    },
+    thread,
+};
+
+use bevy::prelude::*;
+use chess_core::net::{Message, NetError, Peer};
+
+use crate::game::{Game, GameMode};
+
+pub struct NetworkPlugin;
+
+impl Plugin for NetworkPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            receive_remote_moves.run_if(resource_exists::<Remote>),
+        )
+        .add_systems(
+            PostUpdate,
+            send_local_moves.run_if(resource_exists::<Remote>.and_then(resource_changed::<Game>)),
+        )
+        .add_systems(
+            Last,
+            say_goodbye.run_if(resource_exists::<Remote>.and_then(on_event::<AppExit>())),
+        );
+    }
+}
+
+/// The other player of a game over the network. Messages are read on a
+/// separate thread so waiting for them does not block the frame loop.
+#[derive(Resource)]
+pub struct Remote {
+    peer: Peer,
+    messages: Mutex<Receiver<Result<Message, NetError>>>,
+    /// Number of moves of the game both players know about.
+    synced: usize,
+}
+
+impl Remote {
+    pub fn new(peer: Peer) -> Result<Remote, NetError> {
+        let mut reader = peer.try_clone()?;
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || loop {
+            let message = reader.receive();
+            let is_last = !matches!(message, Ok(Message::Move(_)));
+            if sender.send(message).is_err() || is_last {
+                break;
+            }
+        });
+
+        Ok(Remote {
+            peer,
+            messages: Mutex::new(receiver),
+            synced: 0,
+        })
+    }
+}
+
+/// Applies the moves of the other player, once checked to be legal and
+/// played in turn.
+fn receive_remote_moves(
+    mut commands: Commands,
+    mut remote: ResMut<Remote>,
+    mut game: ResMut<Game>,
+    mut game_mode: ResMut<GameMode>,
+) {
+    let remote = &mut *remote;
+    let messages = remote.messages.lock().expect("message lock poisoned");
+
+    loop {
+        match messages.try_recv() {
+            Ok(Ok(Message::Move(mv))) => {
+                let san = game.is_legal(mv).then(|| game.to_san(mv));
+                match remote.peer.apply_remote(&mut game, mv) {
+                    Ok(()) => {
+                        info!("Opponent plays {}", san.unwrap_or_default());
+                        remote.synced += 1;
+                    }
+                    Err(err) => warn!("Rejected move from the opponent: {}", err),
+                }
+            }
+            Ok(Ok(message @ Message::Hello { .. })) => {
+                warn!("Unexpected message from the opponent: {}", message);
+            }
+            Ok(Ok(Message::Bye) | Err(NetError::Disconnected))
+            | Err(TryRecvError::Disconnected) => {
+                info!("The opponent left, both sides are now played here");
+                *game_mode = GameMode::HumanVsHuman;
+                commands.remove_resource::<Remote>();
+                break;
+            }
+            Ok(Err(err)) => {
+                warn!("Lost the connection to the opponent: {}", err);
+                *game_mode = GameMode::HumanVsHuman;
+                commands.remove_resource::<Remote>();
+                break;
+            }
+            Err(TryRecvError::Empty) => break,
+        }
+    }
+}
+
+/// Sends the moves played on this side, whether by dropping a piece or
+/// choosing a promotion.
+fn send_local_moves(mut remote: ResMut<Remote>, game: Res<Game>) {
+    let remote = &mut *remote;
+    let new_moves: Vec<_> = game.moves().skip(remote.synced).collect();
+
+    for mv in new_moves {
+        if let Err(err) = remote.peer.send(&Message::Move(mv)) {
+            warn!("Cannot send move {} to the opponent: {}", mv, err);
+            return;
+        }
+        remote.synced += 1;
+    }
+}
+
+/// Tells the other player this side is leaving when the app exits.
+fn say_goodbye(mut remote: ResMut<Remote>) {
+    if let Err(err) = remote.peer.send(&Message::Bye) {
+        warn!("Cannot say goodbye to the opponent: {}", err);
+    }
+}
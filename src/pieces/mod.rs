@@ -1,122 +1,162 @@
-mod bishop;
-mod king;
-mod knight;
-mod pawn;
-mod queen;
-mod rook;
-mod utils;
-
 use bevy::prelude::*;
-use bishop::Bishop;
-use king::King;
-use knight::Knight;
-use pawn::Pawn;
-use queen::Queen;
-use rook::Rook;
+use chess_core::{Color, PieceType, Square};
 
-use crate::board::get_pixels_by_pos;
+use crate::{
+    board::{get_pixels_by_square, Upright, TILE_SIZE},
+    game::Game,
+};
 
 const SPRITE_SIZE: f32 = 480.0;
 
-#[derive(Resource, Clone, Debug, PartialEq, Eq)]
-pub enum Color {
-    Black,
-    White,
-}
-
-impl Color {
-    pub fn switch(&mut self) {
-        *self = match *self {
-            Color::Black => Color::White,
-            Color::White => Color::Black,
-        }
-    }
-}
+/// Seconds a piece takes to slide to its new square.
+const MOVE_DURATION: f32 = 0.2;
 
-trait Kind {
-    fn get_valid_moves(
-        &self,
-        current_position: IVec2,
-        color: &Color,
-        pieces_on_board: Vec<&Piece>,
-    ) -> Vec<IVec2>;
-}
+pub struct PiecesPlugin;
 
-trait BuildPieceKind {
-    fn new() -> Box<impl Kind + Send + Sync + 'static>;
-    fn get_initial_board_position_indices() -> (Vec<IVec2>, Vec<IVec2>);
-    fn get_sprites() -> (Rect, Rect);
+impl Plugin for PiecesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, animate_pieces)
+            .add_systems(PostUpdate, sync_pieces.run_if(resource_changed::<Game>));
+    }
 }
 
+/// Sprite of a piece on the board, mirroring a piece of the game position.
 #[derive(Component)]
 pub struct Piece {
-    pub position: IVec2,
+    pub square: Square,
     pub color: Color,
-    kind: Box<dyn Kind + Send + Sync + 'static>,
+    pub piece_type: PieceType,
 }
 
 impl Piece {
-    pub fn get_valid_moves(&self, pieces_on_board: Vec<&Piece>) -> Vec<IVec2> {
-        self.kind
-            .get_valid_moves(self.position, &self.color, pieces_on_board)
+    fn matches(&self, piece: chess_core::Piece) -> bool {
+        self.color == piece.color && self.piece_type == piece.piece_type
     }
+}
+
+/// Slides a piece sprite from where it was to the square of its `Piece`.
+#[derive(Component)]
+struct Slide {
+    start: Vec2,
+    timer: Timer,
+}
+
+fn get_sprite_by_index(indices: IVec2) -> Rect {
+    let xi = indices.x as f32;
+    let yi = indices.y as f32;
+
+    Rect::new(
+        xi * SPRITE_SIZE,
+        yi * SPRITE_SIZE,
+        (xi + 1.0) * SPRITE_SIZE,
+        (yi + 1.0) * SPRITE_SIZE,
+    )
+}
 
-    fn new<T: BuildPieceKind>(position: IVec2, color: Color) -> Piece {
-        Piece {
-            position,
-            color,
-            kind: T::new(),
+pub fn get_sprite(piece_type: PieceType, color: Color) -> Rect {
+    let x = match piece_type {
+        PieceType::King => 0,
+        PieceType::Queen => 1,
+        PieceType::Bishop => 2,
+        PieceType::Knight => 3,
+        PieceType::Rook => 4,
+        PieceType::Pawn => 5,
+    };
+
+    let y = match color {
+        Color::White => 0,
+        Color::Black => 1,
+    };
+
+    get_sprite_by_index(IVec2::new(x, y))
+}
+
+/// Spawns, moves and despawns piece sprites so they match the game position.
+fn sync_pieces(
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    game: Res<Game>,
+    mut pieces_query: Query<(Entity, &mut Piece, &Transform)>,
+) {
+    let mut missing_pieces: Vec<(Square, chess_core::Piece)> = game
+        .pieces()
+        .filter(|(square, piece)| {
+            !pieces_query
+                .iter()
+                .any(|(_, sprite, _)| sprite.square == *square && sprite.matches(*piece))
+        })
+        .collect();
+
+    for (entity, mut piece, transform) in &mut pieces_query {
+        if game
+            .piece_at(piece.square)
+            .is_some_and(|p| piece.matches(p))
+        {
+            continue;
         }
-    }
 
-    fn spawn<T: BuildPieceKind>(commands: &mut Commands, texture: Handle<Image>) {
-        let (white_sprite, black_sprite) = T::get_sprites();
-        let (w_positions, b_positions) = T::get_initial_board_position_indices();
-
-        for position in w_positions {
-            let pixel_pos = get_pixels_by_pos(position);
-            commands.spawn((
-                SpriteBundle {
-                    transform: Transform::from_xyz(pixel_pos.x, pixel_pos.y, 1.),
-                    texture: texture.clone(),
-                    sprite: Sprite {
-                        custom_size: Some(Vec2::new(100.0, 100.0)),
-                        rect: Some(white_sprite),
-                        ..default()
-                    },
-                    ..default()
-                },
-                Piece::new::<T>(position, Color::White),
-            ));
+        // Reuse the sprite of a piece that moved, otherwise it was captured
+        match missing_pieces.iter().position(|(_, p)| piece.matches(*p)) {
+            Some(index) => {
+                let (square, _) = missing_pieces.swap_remove(index);
+
+                piece.square = square;
+                commands.entity(entity).insert(Slide {
+                    start: transform.translation.truncate(),
+                    timer: Timer::from_seconds(MOVE_DURATION, TimerMode::Once),
+                });
+            }
+            None => commands.entity(entity).despawn(),
         }
+    }
+
+    let texture = server.load("sprites/pieces.png");
+
+    for (square, piece) in missing_pieces {
+        let pixel_pos = get_pixels_by_square(square);
 
-        for position in b_positions {
-            let pixel_pos = get_pixels_by_pos(position);
-
-            commands.spawn((
-                SpriteBundle {
-                    transform: Transform::from_xyz(pixel_pos.x, pixel_pos.y, 1.),
-                    texture: texture.clone(),
-                    sprite: Sprite {
-                        custom_size: Some(Vec2::new(100.0, 100.0)),
-                        rect: Some(black_sprite),
-                        ..default()
-                    },
+        commands.spawn((
+            SpriteBundle {
+                transform: Transform::from_xyz(pixel_pos.x, pixel_pos.y, 1.),
+                texture: texture.clone(),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::new(TILE_SIZE, TILE_SIZE)),
+                    rect: Some(get_sprite(piece.piece_type, piece.color)),
                     ..default()
                 },
-                Piece::new::<T>(position, Color::Black),
-            ));
-        }
+                ..default()
+            },
+            Piece {
+                square,
+                color: piece.color,
+                piece_type: piece.piece_type,
+            },
+            Upright,
+        ));
     }
 }
 
-pub fn spawn_pieces(mut commands: Commands, server: Res<AssetServer>) {
-    let texture = server.load("sprites/pieces.png");
-
-    Piece::spawn::<King>(&mut commands, texture.clone());
-    Piece::spawn::<Queen>(&mut commands, texture.clone());
-    Piece::spawn::<Bishop>(&mut commands, texture.clone());
-    Piece::spawn::<Knight>(&mut commands, texture.clone());
-    Piece::spawn::<Rook>(&mut commands, texture.clone());
-    Piece::spawn::<Pawn>(&mut commands, texture.clone());
+fn animate_pieces(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &Piece, &mut Slide, &mut Transform)>,
+) {
+    for (entity, piece, mut slide, mut transform) in &mut query {
+        slide.timer.tick(time.delta());
+
+        let t = slide.timer.fraction();
+        let eased = t * t * (3.0 - 2.0 * t);
+        let position = slide.start.lerp(get_pixels_by_square(piece.square), eased);
+
+        // Sliding pieces are drawn above the others until they land
+        let z = match slide.timer.finished() {
+            true => 1.0,
+            false => 2.0,
+        };
+        transform.translation = position.extend(z);
+
+        if slide.timer.finished() {
+            commands.entity(entity).remove::<Slide>();
+        }
+    }
 }
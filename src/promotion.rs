@@ -0,0 +1,154 @@
+use bevy::prelude::*;
+use chess_core::{Move, PieceType, Square};
+
+use crate::{
+    board::{get_pixels_by_square, BoardCursor, Upright, TILE_SIZE},
+    game::Game,
+    pieces::{get_sprite, Piece},
+};
+
+pub struct PromotionPlugin;
+
+impl Plugin for PromotionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<Promote>().add_systems(
+            Update,
+            (
+                show_promotion_options,
+                choose_promotion,
+                promote_piece,
+                hide_promotion_options,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Marks a pawn that reached the last rank and is waiting for a promotion choice.
+#[derive(Component)]
+pub struct Promoting {
+    pub from: Square,
+    pub to: Square,
+}
+
+/// Promotes the pawn waiting on the last rank, so promotions can be chosen
+/// from code as well as from the on-board picker.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct Promote(pub PieceType);
+
+#[derive(Component)]
+struct PromotionOverlay;
+
+pub fn promotion_pending(promoting_query: Query<(), With<Promoting>>) -> bool {
+    !promoting_query.is_empty()
+}
+
+#[derive(Component)]
+struct PromotionOption {
+    square: Square,
+    piece_type: PieceType,
+}
+
+fn show_promotion_options(
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    promoting_query: Query<(&Piece, &Promoting), Added<Promoting>>,
+) {
+    for (piece, promoting) in &promoting_query {
+        let texture = server.load("sprites/pieces.png");
+
+        // Stack the options from the promotion tile towards the center of the board
+        let direction = match promoting.to.rank() == 7 {
+            true => -1,
+            false => 1,
+        };
+
+        commands
+            .spawn((PromotionOverlay, SpatialBundle::default()))
+            .with_children(|parent| {
+                for (i, piece_type) in PieceType::PROMOTIONS.into_iter().enumerate() {
+                    let Some(square) = promoting.to.offset(0, direction * i as i32) else {
+                        continue;
+                    };
+                    let pixel_pos = get_pixels_by_square(square);
+
+                    parent.spawn(SpriteBundle {
+                        transform: Transform::from_xyz(pixel_pos.x, pixel_pos.y, 3.0),
+                        sprite: Sprite {
+                            color: Color::srgba(0.9, 0.9, 0.9, 0.95),
+                            custom_size: Some(Vec2::new(TILE_SIZE, TILE_SIZE)),
+                            ..default()
+                        },
+                        ..default()
+                    });
+
+                    parent.spawn((
+                        PromotionOption { square, piece_type },
+                        Upright,
+                        SpriteBundle {
+                            transform: Transform::from_xyz(pixel_pos.x, pixel_pos.y, 4.0),
+                            texture: texture.clone(),
+                            sprite: Sprite {
+                                custom_size: Some(Vec2::new(TILE_SIZE, TILE_SIZE)),
+                                rect: Some(get_sprite(piece_type, piece.color)),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                    ));
+                }
+            });
+    }
+}
+
+fn choose_promotion(
+    buttons: Res<ButtonInput<MouseButton>>,
+    options_query: Query<&PromotionOption>,
+    cursor: BoardCursor,
+    mut promote_writer: EventWriter<Promote>,
+) {
+    if buttons.just_pressed(MouseButton::Left) {
+        if let Some(clicked_square) = cursor.square() {
+            if let Some(option) = options_query
+                .iter()
+                .find(|option| option.square == clicked_square)
+            {
+                promote_writer.send(Promote(option.piece_type));
+            }
+        }
+    }
+}
+
+fn promote_piece(
+    mut commands: Commands,
+    mut promote_reader: EventReader<Promote>,
+    mut game: ResMut<Game>,
+    promoting_query: Query<(Entity, &Promoting)>,
+) {
+    for Promote(piece_type) in promote_reader.read() {
+        if let Ok((entity, promoting)) = promoting_query.get_single() {
+            let mv = Move::with_promotion(promoting.from, promoting.to, *piece_type);
+
+            if !game.is_legal(mv) {
+                warn!("Cannot promote a pawn to {:?}", piece_type);
+                continue;
+            }
+
+            game.make_move(mv);
+            commands.entity(entity).remove::<Promoting>();
+            break;
+        }
+    }
+}
+
+fn hide_promotion_options(
+    mut commands: Commands,
+    mut removed: RemovedComponents<Promoting>,
+    overlay_query: Query<Entity, With<PromotionOverlay>>,
+) {
+    if removed.read().count() > 0 {
+        for entity in &overlay_query {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
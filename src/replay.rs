@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+use chess_core::Pgn;
+
+use crate::game::Game;
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ReplayStep>().add_systems(
+            Update,
+            (replay_keys, step_replay)
+                .chain()
+                .run_if(resource_exists::<Replay>),
+        );
+    }
+}
+
+/// A loaded game being stepped through. While it exists, the `Game` holds
+/// the position after the first `game.moves().count()` replay moves.
+#[derive(Resource, Clone, Debug)]
+pub struct Replay {
+    pub pgn: Pgn,
+}
+
+impl Replay {
+    pub fn new(pgn: Pgn) -> Replay {
+        Replay { pgn }
+    }
+}
+
+/// Moves the replay forward, back, or to its first or last position.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplayStep {
+    Forward,
+    Back,
+    First,
+    Last,
+}
+
+fn replay_keys(keys: Res<ButtonInput<KeyCode>>, mut step_writer: EventWriter<ReplayStep>) {
+    if keys.just_pressed(KeyCode::ArrowRight) {
+        step_writer.send(ReplayStep::Forward);
+    }
+    if keys.just_pressed(KeyCode::ArrowLeft) {
+        step_writer.send(ReplayStep::Back);
+    }
+    if keys.just_pressed(KeyCode::Home) || keys.just_pressed(KeyCode::ArrowUp) {
+        step_writer.send(ReplayStep::First);
+    }
+    if keys.just_pressed(KeyCode::End) || keys.just_pressed(KeyCode::ArrowDown) {
+        step_writer.send(ReplayStep::Last);
+    }
+}
+
+fn step_replay(
+    mut step_reader: EventReader<ReplayStep>,
+    replay: Res<Replay>,
+    mut game: ResMut<Game>,
+) {
+    for step in step_reader.read() {
+        let moves = &replay.pgn.moves;
+        let ply = game.moves().count();
+
+        match step {
+            ReplayStep::Forward => {
+                if let Some(mv) = moves.get(ply) {
+                    info!("{}. {}", game.fullmove_number(), game.to_san(*mv));
+                    game.make_move(*mv);
+                }
+            }
+            ReplayStep::Back => {
+                game.unmake_move();
+            }
+            ReplayStep::First => while game.unmake_move().is_some() {},
+            ReplayStep::Last => {
+                for mv in &moves[ply.min(moves.len())..] {
+                    game.make_move(*mv);
+                }
+            }
+        }
+    }
+}